@@ -0,0 +1,90 @@
+use {
+    crate::{create_output, option_value, read_input},
+    cpio_reader::{Converter, DeviceEncoding, Format, IoWriter},
+    std::io::Write,
+};
+
+const USAGE: &str = "\
+Usage:
+    cpio_reader convert [OPTIONS] <INPUT> <OUTPUT>
+
+Options:
+    -H, --format <FORMAT>          The output format: bin, bin-be, odc, newc or crc [default: newc]
+    --device-encoding <ENCODING>   The encoding of device numbers of bin and odc: linux or split:<MINOR_BITS> [default: linux]
+    --block-size <SIZE>            The block size to which the output is padded [default: 512]
+    --skip-unrepresentable         Skips the entries which cannot be represented in the output format
+    -h, --help                     Prints this message";
+
+pub(crate) fn run(args: &[String]) -> Result<(), String> {
+    let mut converter = Converter::new(Format::NewAscii);
+    let mut skip_unrepresentable = false;
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-H" | "--format" => {
+                converter = converter.format(parse_format(option_value(arg, &mut args)?)?);
+            }
+            "--device-encoding" => {
+                converter = converter
+                    .device_encoding(parse_device_encoding(option_value(arg, &mut args)?)?);
+            }
+            "--block-size" => {
+                let size = option_value(arg, &mut args)?;
+
+                converter = converter.block_size(
+                    size.parse()
+                        .map_err(|_| format!("invalid block size: `{size}`"))?,
+                );
+            }
+            "--skip-unrepresentable" => skip_unrepresentable = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ => paths.push(arg.as_str()),
+        }
+    }
+
+    let [input, output] = paths[..] else {
+        return Err(format!("invalid arguments\n\n{USAGE}"));
+    };
+
+    let input = read_input(input)?;
+    let mut sink = IoWriter(create_output(output)?);
+
+    if skip_unrepresentable {
+        converter.convert_skipping(&input, &mut sink, |entry, field| {
+            eprintln!(
+                "cpio_reader: skipped `{}`: the value of `{}` cannot be represented",
+                entry.name(),
+                field
+            );
+        })
+    } else {
+        converter.convert(&input, &mut sink)
+    }
+    .map_err(|e| e.to_string())?;
+
+    sink.0
+        .flush()
+        .map_err(|e| format!("failed to write the output: {e}"))
+}
+
+fn parse_format(format: &str) -> Result<Format, String> {
+    format.parse().map_err(|e| format!("{e}: `{format}`"))
+}
+
+fn parse_device_encoding(encoding: &str) -> Result<DeviceEncoding, String> {
+    if encoding == "linux" {
+        return Ok(DeviceEncoding::Linux);
+    }
+
+    encoding
+        .strip_prefix("split:")
+        .and_then(|bits| bits.parse().ok())
+        .map(|minor_bits| DeviceEncoding::Split { minor_bits })
+        .ok_or_else(|| format!("unknown device encoding: `{encoding}`"))
+}
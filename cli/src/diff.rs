@@ -0,0 +1,92 @@
+use {
+    crate::read_input,
+    cpio_reader::{Changes, Difference},
+};
+
+const USAGE: &str = "\
+Usage:
+    cpio_reader diff [OPTIONS] <OLD> <NEW>
+
+Lists the entries added to, removed from and changed in NEW compared with OLD. Changed entries
+show the old and the new values of the changed metadata.
+
+Options:
+    -h, --help    Prints this message";
+
+pub(crate) fn run(args: &[String]) -> Result<(), String> {
+    let mut paths = Vec::new();
+
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ => paths.push(arg.as_str()),
+        }
+    }
+
+    let [old, new] = paths[..] else {
+        return Err(format!("invalid arguments\n\n{USAGE}"));
+    };
+
+    let old = read_input(old)?;
+    let new = read_input(new)?;
+
+    let differences = cpio_reader::diff(&old, &new);
+
+    for difference in &differences {
+        println!("{}", describe(difference));
+    }
+
+    let count = |f: fn(&Difference<'_>) -> bool| differences.iter().filter(|d| f(d)).count();
+
+    println!(
+        "{} added, {} removed, {} changed ({} metadata only)",
+        count(|d| matches!(d, Difference::Added(_))),
+        count(|d| matches!(d, Difference::Removed(_))),
+        count(|d| matches!(d, Difference::Changed { .. })),
+        count(|d| matches!(d, Difference::Changed { changes, .. } if changes.is_metadata_only())),
+    );
+
+    Ok(())
+}
+
+/// Describes `difference` with the old and the new values of the changed metadata.
+fn describe(difference: &Difference<'_>) -> String {
+    let Difference::Changed { old, new, changes } = difference else {
+        return difference.to_string();
+    };
+
+    let mut details = Vec::new();
+
+    if changes.contains(Changes::CONTENT) {
+        details.push("content".to_owned());
+    }
+
+    if changes.contains(Changes::MODE) {
+        details.push(format!(
+            "mode {:06o} -> {:06o}",
+            old.mode().bits(),
+            new.mode().bits()
+        ));
+    }
+
+    if changes.contains(Changes::UID) {
+        details.push(format!("uid {} -> {}", old.uid(), new.uid()));
+    }
+
+    if changes.contains(Changes::GID) {
+        details.push(format!("gid {} -> {}", old.gid(), new.gid()));
+    }
+
+    if changes.contains(Changes::MTIME) {
+        details.push(format!("mtime {} -> {}", old.modified(), new.modified()));
+    }
+
+    if changes.contains(Changes::RDEV) {
+        details.push(format!("rdev {} -> {}", old.rdevice(), new.rdevice()));
+    }
+
+    format!("~ {}: {}", new.path(), details.join(", "))
+}
@@ -0,0 +1,74 @@
+//! A command-line tool to inspect and convert cpio files.
+
+mod convert;
+mod diff;
+
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufWriter, Read, Write},
+    process,
+};
+
+const USAGE: &str = "\
+Usage:
+    cpio_reader <COMMAND> [OPTIONS] ...
+
+Commands:
+    convert    Converts a cpio file into another format
+    diff       Lists the differences between two cpio files
+
+Use `-` as a path to read from the standard input or to write to the standard output.
+Run `cpio_reader <COMMAND> --help` for the options of each command.";
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let result = match args.split_first() {
+        Some((command, args)) if command == "convert" => convert::run(args),
+        Some((command, args)) if command == "diff" => diff::run(args),
+        Some((help, _)) if help == "-h" || help == "--help" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        _ => Err(format!("invalid arguments\n\n{USAGE}")),
+    };
+
+    if let Err(e) = result {
+        eprintln!("cpio_reader: {e}");
+        process::exit(1);
+    }
+}
+
+fn read_input(path: &str) -> Result<Vec<u8>, String> {
+    if path == "-" {
+        let mut input = Vec::new();
+
+        io::stdin()
+            .read_to_end(&mut input)
+            .map(|_| input)
+            .map_err(|e| format!("failed to read the standard input: {e}"))
+    } else {
+        fs::read(path).map_err(|e| format!("failed to read `{path}`: {e}"))
+    }
+}
+
+fn create_output(path: &str) -> Result<BufWriter<Box<dyn Write>>, String> {
+    let output: Box<dyn Write> = if path == "-" {
+        Box::new(io::stdout())
+    } else {
+        Box::new(File::create(path).map_err(|e| format!("failed to create `{path}`: {e}"))?)
+    };
+
+    Ok(BufWriter::new(output))
+}
+
+/// Returns the value following the option `name`.
+fn option_value<'a>(
+    name: &str,
+    args: &mut impl Iterator<Item = &'a String>,
+) -> Result<&'a str, String> {
+    args.next()
+        .map(String::as_str)
+        .ok_or_else(|| format!("`{name}` requires a value"))
+}
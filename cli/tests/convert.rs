@@ -0,0 +1,43 @@
+use {
+    cpio_reader::HardLinks,
+    std::{fs, path::Path, process::Command},
+};
+
+#[test]
+fn convert_bin_into_newc() {
+    let output = Path::new(env!("CARGO_TARGET_TMPDIR")).join("converted.cpio");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_cpio_reader"))
+        .args(["convert", "--format", "newc", "../tests/bin.cpio"])
+        .arg(&output)
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+
+    let bin = fs::read("../tests/bin.cpio").unwrap();
+    let newc = fs::read(&output).unwrap();
+
+    assert!(
+        cpio_reader::iter_files(&newc).all(|entry| entry.format() == cpio_reader::Format::NewAscii)
+    );
+    let (bin_links, newc_links) = (HardLinks::new(&bin), HardLinks::new(&newc));
+
+    assert!(
+        cpio_reader::iter_files(&bin)
+            .map(|entry| (entry.name(), bin_links.content(&entry)))
+            .eq(cpio_reader::iter_files(&newc)
+                .map(|entry| (entry.name(), newc_links.content(&entry))))
+    );
+}
+
+#[test]
+fn reject_unknown_format() {
+    let output = Command::new(env!("CARGO_BIN_EXE_cpio_reader"))
+        .args(["convert", "--format", "tar", "../tests/bin.cpio", "-"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown format"));
+}
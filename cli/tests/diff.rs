@@ -0,0 +1,16 @@
+use std::process::Command;
+
+#[test]
+fn diff_bin_and_newc() {
+    let output = Command::new(env!("CARGO_BIN_EXE_cpio_reader"))
+        .args(["diff", "../tests/bin.cpio", "../tests/newc.cpio"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("~ magics/rosemary: mode 100644 -> 100751, mtime "));
+    assert!(stdout.ends_with("0 added, 0 removed, 6 changed (6 metadata only)\n"));
+}
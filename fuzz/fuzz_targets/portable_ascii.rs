@@ -0,0 +1,14 @@
+//! Feeds inputs starting with the magic value of Portable ASCII Format to exercise its
+//! interpreter.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+const MAGIC: &[u8] = b"070707";
+
+fuzz_target!(|data: &[u8]| {
+    let data = [MAGIC, data].concat();
+
+    cpio_reader_fuzz::check_entries(&data);
+});
@@ -0,0 +1,75 @@
+//! Checks shared by the fuzz targets.
+
+use cpio_reader::{ArchiveEditor, Entry};
+
+/// Checks the invariants of the entries read from `data`.
+pub fn check_entries(data: &[u8]) {
+    let mut next_header_offset = 0;
+
+    let entries = cpio_reader::iter_files(data).collect::<Vec<_>>();
+
+    for entry in &entries {
+        assert_eq!(entry.header_offset(), next_header_offset);
+        assert_eq!(
+            &data[entry.data_offset()..entry.data_offset() + entry.file().len()],
+            entry.file()
+        );
+        assert_eq!(entry.size(), entry.file().len() as u64);
+
+        next_header_offset = entry.header_offset() + entry.entry_len();
+    }
+
+    let mut copy = data.to_vec();
+
+    let names = cpio_reader::iter_files_mut(&mut copy)
+        .map(|entry| entry.name().to_owned())
+        .collect::<Vec<_>>();
+
+    assert!(names.iter().eq(entries.iter().map(Entry::name)));
+}
+
+/// Checks that the entries read from `data` are read back identically after writing them.
+pub fn check_round_trip(data: &[u8]) {
+    for entry in cpio_reader::iter_files(data) {
+        let mut written = Vec::new();
+
+        entry
+            .write_to(&mut written)
+            .expect("An entry read from a cpio file must be representable in its format.");
+
+        let mut reread = cpio_reader::iter_files(&written);
+
+        assert_same_entry(&reread.next().unwrap(), &entry);
+        assert!(reread.next().is_none());
+    }
+
+    if let Some(editor) = ArchiveEditor::new(data) {
+        let mut written = Vec::new();
+
+        editor.write_to(&mut written).unwrap();
+
+        for (reread, entry) in cpio_reader::iter_files(&written).zip(cpio_reader::iter_files(data))
+        {
+            assert_same_entry(&reread, &entry);
+        }
+    }
+}
+
+fn assert_same_entry(a: &Entry<'_>, b: &Entry<'_>) {
+    assert_eq!(a.format(), b.format());
+    assert_eq!(a.name(), b.name());
+    assert_eq!(a.file(), b.file());
+    assert_eq!(a.mode(), b.mode());
+    assert_eq!(a.uid(), b.uid());
+    assert_eq!(a.gid(), b.gid());
+    assert_eq!(a.ino(), b.ino());
+    assert_eq!(a.nlink(), b.nlink());
+    assert_eq!(a.mtime(), b.mtime());
+    assert_eq!(a.dev(), b.dev());
+    assert_eq!(a.devmajor(), b.devmajor());
+    assert_eq!(a.devminor(), b.devminor());
+    assert_eq!(a.rdev(), b.rdev());
+    assert_eq!(a.rdevmajor(), b.rdevmajor());
+    assert_eq!(a.rdevminor(), b.rdevminor());
+    assert_eq!(a.check(), b.check());
+}
@@ -0,0 +1,143 @@
+/* The C API of cpio_reader, generated by cbindgen from `src/capi.rs`. Do not edit. */
+
+#ifndef CPIO_READER_H
+#define CPIO_READER_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// The format of a cpio file.
+typedef enum CpioFormat {
+  // Old Binary Format in little endian.
+  CPIO_FORMAT_OLD_BINARY_LITTLE,
+  // Old Binary Format in big endian.
+  CPIO_FORMAT_OLD_BINARY_BIG,
+  // Portable ASCII Format.
+  CPIO_FORMAT_PORTABLE_ASCII,
+  // New ASCII Format.
+  CPIO_FORMAT_NEW_ASCII,
+  // New CRC Format.
+  CPIO_FORMAT_NEW_CRC,
+} CpioFormat;
+
+// An iterator over the entries of a cpio file.
+//
+// The fields are private. Initialize the iterator with [`cpio_iter_init`].
+typedef struct CpioIter {
+  const uint8_t *binary;
+  size_t len;
+  size_t offset;
+} CpioIter;
+
+// The metadata of an entry.
+//
+// The device numbers are split with the Linux encoding for Old Binary Format and Portable ASCII
+// Format.
+typedef struct CpioMetadata {
+  // The format of the entry.
+  enum CpioFormat format;
+  // The mode as it is stored in the header.
+  uint32_t mode;
+  // The inode number.
+  uint32_t ino;
+  // The user ID.
+  uint32_t uid;
+  // The group ID.
+  uint32_t gid;
+  // The number of links.
+  uint32_t nlink;
+  // The modification time in seconds since the Unix epoch.
+  uint64_t mtime;
+  // The major number of the device which contains the file.
+  uint32_t dev_major;
+  // The minor number of the device which contains the file.
+  uint32_t dev_minor;
+  // The major number of the file if it is a special device.
+  uint32_t rdev_major;
+  // The minor number of the file if it is a special device.
+  uint32_t rdev_minor;
+  // Whether `check` is stored in the header, which is the case only for New CRC Format.
+  bool has_check;
+  // The checksum stored in the header.
+  uint32_t check;
+} CpioMetadata;
+
+// An entry of a cpio file.
+//
+// The pointers point to the cpio file passed to [`cpio_iter_init`], so they are valid as long as
+// the cpio file is.
+typedef struct CpioEntry {
+  // The filename, which is not null-terminated.
+  const uint8_t *name;
+  // The length of the filename in bytes.
+  size_t name_len;
+  // The content of the file.
+  const uint8_t *data;
+  // The length of the content in bytes.
+  size_t data_len;
+  // The metadata of the file.
+  struct CpioMetadata metadata;
+} CpioEntry;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Initializes `iter` to iterate over the entries of the cpio file of `len` bytes at `binary`.
+//
+// This function returns `false` if `iter` is null, or if `binary` is null and `len` is not 0.
+//
+// # Safety
+//
+// `iter` must be null or valid for writes. `binary` must be null or valid for reads of `len`
+// bytes, and the bytes must not be modified while the iterator and the entries are used.
+bool cpio_iter_init(struct CpioIter *iter, const uint8_t *binary, size_t len);
+
+// Reads the next entry into `entry`, and returns `true` if it exists.
+//
+// This function returns `false` at the end of the cpio file, at a corrupt entry, or if either of
+// the arguments is null. The function never reads outside of the cpio file.
+//
+// # Safety
+//
+// `iter` must be null or a pointer to an iterator initialized by [`cpio_iter_init`]. `entry` must
+// be null or valid for writes.
+bool cpio_iter_next(struct CpioIter *iter, struct CpioEntry *entry);
+
+// Returns the filename of `entry`, which is not null-terminated, and stores its length in
+// `len`.
+//
+// This function returns null if `entry` is null. `len` may be null.
+//
+// # Safety
+//
+// `entry` must be null or a pointer to an entry read by [`cpio_iter_next`]. `len` must be null
+// or valid for writes.
+const uint8_t *cpio_entry_name(const struct CpioEntry *entry, size_t *len);
+
+// Returns the content of `entry`, and stores its length in `len`.
+//
+// This function returns null if `entry` is null. `len` may be null.
+//
+// # Safety
+//
+// `entry` must be null or a pointer to an entry read by [`cpio_iter_next`]. `len` must be null
+// or valid for writes.
+const uint8_t *cpio_entry_data(const struct CpioEntry *entry, size_t *len);
+
+// Copies the metadata of `entry` into `metadata`.
+//
+// This function returns `false` if either of the arguments is null.
+//
+// # Safety
+//
+// `entry` must be null or a pointer to an entry read by [`cpio_iter_next`]. `metadata` must be
+// null or valid for writes.
+bool cpio_entry_metadata(const struct CpioEntry *entry, struct CpioMetadata *metadata);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CPIO_READER_H */
@@ -0,0 +1,468 @@
+//! Python bindings of `cpio_reader`, which are imported as the `cpio_reader` module.
+//!
+//! ```python
+//! import cpio_reader
+//!
+//! archive = cpio_reader.Archive.open("initramfs.cpio")
+//!
+//! for entry in archive:
+//!     print(entry.filemode, entry.name)
+//!
+//! init = archive.get("init")
+//! cpio = cpio_reader.write([init, cpio_reader.Entry("etc", mode=0o40755)], format="crc")
+//! ```
+
+use {
+    cpio_reader::{Converter, EntryBuilder, EntryPath, Format, HardLinks, Mode},
+    pyo3::{
+        exceptions::PyValueError,
+        prelude::*,
+        types::{PyBytes, PyString},
+    },
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// The `cpio_reader` module.
+///
+/// # Errors
+///
+/// This function returns an error if adding the classes or the functions to `module` fails.
+#[pymodule]
+#[pyo3(name = "cpio_reader")]
+pub fn cpio_reader_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Entry>()?;
+    module.add_class::<Archive>()?;
+    module.add_class::<EntryIter>()?;
+    module.add_function(wrap_pyfunction!(iter_files, module)?)?;
+    module.add_function(wrap_pyfunction!(write, module)?)?;
+
+    Ok(())
+}
+
+/// Returns an iterator over the entries of the given cpio file.
+///
+/// The entries after a corrupt one are ignored.
+#[pyfunction]
+fn iter_files(cpio_binary: Py<PyBytes>) -> EntryIter {
+    EntryIter::new(cpio_binary)
+}
+
+/// Writes the given entries into a cpio file of `format` and returns it.
+///
+/// `format` is one of `bin`, `bin-be`, `odc`, `newc` and `crc`. The device numbers are combined
+/// with the Linux encoding for `bin`, `bin-be` and `odc`, and the checksums are calculated for
+/// `crc`.
+#[pyfunction]
+#[pyo3(signature = (entries, format = "newc"))]
+fn write<'py>(
+    py: Python<'py>,
+    entries: &Bound<'py, PyAny>,
+    format: &str,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let format = format
+        .parse::<Format>()
+        .map_err(|e| PyValueError::new_err(format!("{e}: `{format}`")))?;
+    let converter = Converter::new(format);
+    let mut cpio = Vec::new();
+
+    for entry in entries.try_iter()? {
+        let entry = entry?.downcast_into::<Entry>()?;
+        let entry = entry.get();
+
+        converter
+            .convert_entry(&entry.build(py), &mut cpio)
+            .map_err(|e| PyValueError::new_err(format!("failed to write `{}`: {e}", entry.name)))?;
+    }
+
+    cpio_reader::write_trailer(format, &mut cpio)
+        .map_err(|e| PyValueError::new_err(format!("failed to write the trailer: {e}")))?;
+
+    Ok(PyBytes::new(py, &cpio))
+}
+
+/// An entry of a cpio file.
+///
+/// An entry is read from a cpio file, or created with the constructor to write it with
+/// `cpio_reader.write`. The device numbers are pairs of the major and the minor numbers, which are
+/// split with the Linux encoding for `bin`, `bin-be` and `odc`.
+#[pyclass(frozen, module = "cpio_reader")]
+#[derive(Debug)]
+pub struct Entry {
+    name: String,
+    format: Format,
+    mode: u32,
+    ino: u32,
+    uid: u32,
+    gid: u32,
+    nlink: u32,
+    mtime: u64,
+    device: (u32, u32),
+    rdevice: (u32, u32),
+    check: Option<u32>,
+    data: Py<PyBytes>,
+}
+impl Entry {
+    fn from_entry(py: Python<'_>, entry: &cpio_reader::Entry<'_>) -> Self {
+        Self {
+            name: entry.name().to_owned(),
+            format: entry.format(),
+            mode: entry.raw_mode(),
+            ino: entry.ino(),
+            uid: entry.uid(),
+            gid: entry.gid(),
+            nlink: entry.nlink(),
+            mtime: entry.mtime(),
+            device: entry.device().into(),
+            rdevice: entry.rdevice().into(),
+            check: entry.check(),
+            data: PyBytes::new(py, entry.file()).unbind(),
+        }
+    }
+
+    /// Builds the entry in New ASCII Format, which the converter writes in any format.
+    fn build<'a>(&'a self, py: Python<'_>) -> cpio_reader::Entry<'a> {
+        EntryBuilder::new(&self.name, Format::NewAscii)
+            .devmajor(self.device.0)
+            .devminor(self.device.1)
+            .ino(self.ino)
+            .mode(Mode::from_bits_retain(self.mode))
+            .uid(self.uid)
+            .gid(self.gid)
+            .nlink(self.nlink)
+            .rdevmajor(self.rdevice.0)
+            .rdevminor(self.rdevice.1)
+            .mtime(self.mtime)
+            .file(self.data.as_bytes(py))
+            .build()
+    }
+
+    fn file_type(&self) -> Mode {
+        Mode::from_bits_retain(self.mode).file_type()
+    }
+}
+#[pymethods]
+impl Entry {
+    /// Creates a new entry in the `newc` format.
+    ///
+    /// As `EntryBuilder` of the Rust crate, `mode` includes the file type, such as `0o100644`
+    /// for a regular file.
+    #[new]
+    #[pyo3(signature = (
+        name,
+        data = None,
+        *,
+        mode = 0,
+        ino = 0,
+        uid = 0,
+        gid = 0,
+        nlink = 1,
+        mtime = 0,
+        device = (0, 0),
+        rdevice = (0, 0),
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        py: Python<'_>,
+        name: String,
+        data: Option<Py<PyBytes>>,
+        mode: u32,
+        ino: u32,
+        uid: u32,
+        gid: u32,
+        nlink: u32,
+        mtime: u64,
+        device: (u32, u32),
+        rdevice: (u32, u32),
+    ) -> Self {
+        Self {
+            name,
+            format: Format::NewAscii,
+            mode,
+            ino,
+            uid,
+            gid,
+            nlink,
+            mtime,
+            device,
+            rdevice,
+            check: None,
+            data: data.unwrap_or_else(|| PyBytes::new(py, &[]).unbind()),
+        }
+    }
+
+    /// The filename.
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The format of the entry, which is one of `bin`, `bin-be`, `odc`, `newc` and `crc`.
+    #[getter]
+    fn format(&self) -> String {
+        self.format.to_string()
+    }
+
+    /// The mode as it is stored in the header, including the file type.
+    #[getter]
+    fn mode(&self) -> u32 {
+        self.mode
+    }
+
+    /// The mode in the form of `ls -l`, such as `-rw-r--r--`.
+    #[getter]
+    fn filemode(&self) -> String {
+        Mode::from_bits_retain(self.mode).to_string()
+    }
+
+    /// The inode number.
+    #[getter]
+    fn ino(&self) -> u32 {
+        self.ino
+    }
+
+    /// The user ID.
+    #[getter]
+    fn uid(&self) -> u32 {
+        self.uid
+    }
+
+    /// The group ID.
+    #[getter]
+    fn gid(&self) -> u32 {
+        self.gid
+    }
+
+    /// The number of links.
+    #[getter]
+    fn nlink(&self) -> u32 {
+        self.nlink
+    }
+
+    /// The modification time in seconds since the Unix epoch.
+    #[getter]
+    fn mtime(&self) -> u64 {
+        self.mtime
+    }
+
+    /// The major and the minor numbers of the device which contains the file.
+    #[getter]
+    fn device(&self) -> (u32, u32) {
+        self.device
+    }
+
+    /// The major and the minor numbers of the file if it is a special device.
+    #[getter]
+    fn rdevice(&self) -> (u32, u32) {
+        self.rdevice
+    }
+
+    /// The checksum stored in the header, which is `None` except for the `crc` format.
+    #[getter]
+    fn check(&self) -> Option<u32> {
+        self.check
+    }
+
+    /// The content of the file.
+    #[getter]
+    fn data(&self, py: Python<'_>) -> Py<PyBytes> {
+        self.data.clone_ref(py)
+    }
+
+    /// The length of the content in bytes.
+    #[getter]
+    fn size(&self, py: Python<'_>) -> usize {
+        self.data.as_bytes(py).len()
+    }
+
+    /// The target of the symbolic link, which is `None` if the file is not a symbolic link or if
+    /// the target is not valid UTF-8.
+    #[getter]
+    fn symlink_target(&self, py: Python<'_>) -> Option<&str> {
+        if self.file_type() == Mode::SYMBOLIK_LINK {
+            std::str::from_utf8(self.data.as_bytes(py)).ok()
+        } else {
+            None
+        }
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "Entry(name={}, filemode='{}', size={})",
+            PyString::new(py, &self.name).repr()?,
+            self.filemode(),
+            self.size(py)
+        ))
+    }
+}
+
+/// The entries of a cpio file.
+///
+/// Iterating over an archive yields its entries. The entries after a corrupt one are ignored.
+#[pyclass(frozen, module = "cpio_reader")]
+#[derive(Debug)]
+pub struct Archive {
+    cpio: Py<PyBytes>,
+}
+#[pymethods]
+impl Archive {
+    /// Reads the entries of the given cpio file.
+    #[new]
+    fn new(cpio_binary: Py<PyBytes>) -> Self {
+        Self { cpio: cpio_binary }
+    }
+
+    /// Reads the entries of the cpio file at `path`.
+    #[staticmethod]
+    fn open(py: Python<'_>, path: PathBuf) -> PyResult<Self> {
+        Ok(Self::new(PyBytes::new(py, &fs::read(path)?).unbind()))
+    }
+
+    /// Returns the entry of the given path, or `None` if it does not exist.
+    ///
+    /// The paths are compared after normalization, so `./bin/sh` matches `bin/sh`. If multiple
+    /// entries have the path, the last one is returned as extracting the cpio file leaves it.
+    #[allow(clippy::needless_pass_by_value)]
+    fn get(&self, py: Python<'_>, path: &str) -> Option<Entry> {
+        let path = EntryPath::new(path);
+
+        cpio_reader::iter_files(self.cpio.as_bytes(py))
+            .filter(|entry| entry.path() == path)
+            .last()
+            .map(|entry| Entry::from_entry(py, &entry))
+    }
+
+    /// Extracts the directories, the regular files and the symbolic links into the directory at
+    /// `path`.
+    ///
+    /// The other types of files, such as special devices, are skipped. The hard links without
+    /// contents in the `newc` and `crc` formats are written with the content stored in another
+    /// link to the same file. This method raises `ValueError` if an entry escapes `path`, or if
+    /// it would be written through a symbolic link.
+    #[allow(clippy::needless_pass_by_value)]
+    fn extract(&self, py: Python<'_>, path: PathBuf) -> PyResult<()> {
+        let cpio = self.cpio.as_bytes(py);
+        let links = HardLinks::new(cpio);
+
+        let mut directories = Vec::new();
+
+        for entry in cpio_reader::iter_files(cpio) {
+            let target = entry.path().join_to(&path).ok_or_else(|| {
+                PyValueError::new_err(format!("`{}` escapes the destination", entry.name()))
+            })?;
+
+            check_no_symlinks(&path, &target)?;
+
+            match entry.mode().file_type() {
+                Mode::DIRECTORY => {
+                    fs::create_dir_all(&target)?;
+                    directories.push((target, entry.mode()));
+                }
+                Mode::REGULAR_FILE => {
+                    create_parent(&target)?;
+                    fs::write(&target, links.content(&entry))?;
+                    set_permissions(&target, entry.mode())?;
+                }
+                #[cfg(unix)]
+                Mode::SYMBOLIK_LINK => {
+                    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+                    create_parent(&target)?;
+                    std::os::unix::fs::symlink(OsStr::from_bytes(entry.file()), &target)?;
+                }
+                _ => {}
+            }
+        }
+
+        // The permissions of the directories are set last so that a read-only directory does not
+        // prevent writing its children.
+        for (directory, mode) in directories.iter().rev() {
+            set_permissions(directory, *mode)?;
+        }
+
+        Ok(())
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> EntryIter {
+        EntryIter::new(self.cpio.clone_ref(py))
+    }
+
+    fn __len__(&self, py: Python<'_>) -> usize {
+        cpio_reader::iter_files(self.cpio.as_bytes(py)).count()
+    }
+
+    fn __contains__(&self, py: Python<'_>, path: &str) -> bool {
+        let path = EntryPath::new(path);
+
+        cpio_reader::iter_files(self.cpio.as_bytes(py)).any(|entry| entry.path() == path)
+    }
+}
+
+/// An iterator over the entries of a cpio file, which reads an entry each time the next one is
+/// requested.
+#[pyclass(module = "cpio_reader")]
+#[derive(Debug)]
+pub struct EntryIter {
+    cpio: Py<PyBytes>,
+    offset: usize,
+}
+impl EntryIter {
+    fn new(cpio: Py<PyBytes>) -> Self {
+        Self { cpio, offset: 0 }
+    }
+}
+#[pymethods]
+impl EntryIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> Option<Entry> {
+        // Every format aligns the headers to the start of the cpio file, and an entry ends at the
+        // alignment of the next header, so reading from the end of the last entry keeps it.
+        let entry = cpio_reader::iter_files(self.cpio.as_bytes(py).get(self.offset..)?).next()?;
+
+        self.offset += entry.entry_len();
+
+        Some(Entry::from_entry(py, &entry))
+    }
+}
+
+/// Returns an error if `target` or its ancestor under `root` is a symbolic link, which may point
+/// outside of `root`.
+fn check_no_symlinks(root: &Path, target: &Path) -> PyResult<()> {
+    for path in target.ancestors().take_while(|path| *path != root) {
+        if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+            return Err(PyValueError::new_err(format!(
+                "`{}` is a symbolic link",
+                path.display()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn create_parent(path: &Path) -> PyResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_permissions(path: &Path, mode: Mode) -> PyResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(mode.bits() & 0o7777))?;
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_permissions(_: &Path, _: Mode) -> PyResult<()> {
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use {
+    pyo3::{prelude::*, types::PyDict},
+    std::{ffi::CString, fs, path::Path},
+};
+
+/// Runs `code` with the `cpio_reader` module imported, and `tmp` set to a new empty directory.
+fn run(tmp: &str, code: &str) {
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR")).join(tmp);
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+        let result = (|| {
+            let module = pyo3::wrap_pymodule!(cpio_reader_py::cpio_reader_py)(py);
+            let globals = PyDict::new(py);
+
+            globals.set_item("cpio_reader", module)?;
+            globals.set_item("tmp", &tmp)?;
+
+            py.run(&CString::new(code).unwrap(), Some(&globals), None)
+        })();
+
+        if let Err(e) = result {
+            e.print(py);
+            panic!("{}", e);
+        }
+    });
+}
+
+#[test]
+fn read_entries() {
+    run(
+        "read_entries",
+        r#"
+archive = cpio_reader.Archive.open("../tests/newc.cpio")
+
+entries = cpio_reader.iter_files(open("../tests/newc.cpio", "rb").read())
+assert iter(entries) is entries
+assert len(archive) == len(list(entries))
+assert [entry.name for entry in archive][:2] == ["derich", "skills"]
+
+derich = archive.get("./derich")
+assert derich.format == "newc"
+assert derich.filemode == "lrwxrwxrwx"
+assert derich.symlink_target == "skills/derich"
+assert derich.data == b"skills/derich"
+assert (derich.uid, derich.gid, derich.ino) == (1000, 1000, 380)
+assert derich.device == (0, 26)
+assert derich.check is None
+
+assert "skills" in archive
+assert archive.get("missing") is None
+"#,
+    );
+}
+
+#[test]
+fn write_entries() {
+    run(
+        "write_entries",
+        r#"
+entries = [
+    cpio_reader.Entry("etc", mode=0o40755),
+    cpio_reader.Entry("etc/hostname", b"cpio\n", mode=0o100644, uid=1, gid=2, mtime=3),
+    cpio_reader.Entry("dev/null", mode=0o20666, rdevice=(1, 3)),
+]
+
+for format in ["bin", "bin-be", "odc", "newc", "crc"]:
+    archive = cpio_reader.Archive(cpio_reader.write(entries, format=format))
+
+    assert [entry.format for entry in archive] == [format] * 3
+    assert [entry.name for entry in archive] == ["etc", "etc/hostname", "dev/null"]
+
+    hostname = archive.get("etc/hostname")
+    assert (hostname.data, hostname.mode, hostname.uid, hostname.gid, hostname.mtime) == (
+        b"cpio\n", 0o100644, 1, 2, 3
+    )
+    assert archive.get("dev/null").rdevice == (1, 3)
+    assert (hostname.check is not None) == (format == "crc")
+
+newc = open("../tests/newc.cpio", "rb").read()
+assert [entry.data for entry in cpio_reader.Archive(cpio_reader.write(cpio_reader.Archive(newc)))] == [
+    entry.data for entry in cpio_reader.iter_files(newc)
+]
+
+try:
+    cpio_reader.write(entries, format="tar")
+    assert False
+except ValueError as e:
+    assert "unknown format" in str(e)
+
+try:
+    cpio_reader.write([cpio_reader.Entry("large", ino=0x10000)], format="bin")
+    assert False
+except ValueError as e:
+    assert "ino" in str(e)
+"#,
+    );
+}
+
+#[test]
+fn extract_entries() {
+    run(
+        "extract_entries",
+        r#"
+import os
+
+cpio = cpio_reader.write([
+    cpio_reader.Entry("./bin", mode=0o40755),
+    cpio_reader.Entry("bin/busybox", b"busybox", mode=0o100755, ino=1, nlink=2),
+    cpio_reader.Entry("bin/sh", mode=0o100755, ino=1, nlink=2),
+    cpio_reader.Entry("init", b"bin/busybox", mode=0o120777),
+])
+
+cpio_reader.Archive(cpio).extract(tmp)
+
+assert open(os.path.join(tmp, "bin/busybox"), "rb").read() == b"busybox"
+assert open(os.path.join(tmp, "bin/sh"), "rb").read() == b"busybox"
+assert os.stat(os.path.join(tmp, "bin/sh")).st_mode == 0o100755
+assert os.readlink(os.path.join(tmp, "init")) == "bin/busybox"
+"#,
+    );
+}
+
+#[test]
+fn reject_entries_outside_destination() {
+    run(
+        "reject_entries_outside_destination",
+        r#"
+import os
+
+escaping = cpio_reader.write([cpio_reader.Entry("../escaped", b"", mode=0o100644)])
+through_symlink = cpio_reader.write([
+    cpio_reader.Entry("etc", b"/etc", mode=0o120777),
+    cpio_reader.Entry("etc/passwd", b"", mode=0o100644),
+])
+
+for cpio, message in [(escaping, "escapes"), (through_symlink, "symbolic link")]:
+    try:
+        cpio_reader.Archive(cpio).extract(os.path.join(tmp, "root"))
+        assert False
+    except ValueError as e:
+        assert message in str(e)
+
+assert not os.path.exists(os.path.join(tmp, "escaped"))
+"#,
+    );
+}
@@ -0,0 +1,166 @@
+use crate::{checksum, Entry, Format, Metadata, Mode};
+
+/// A builder of [`Entry`], which is used to create entries to write.
+///
+/// The offsets of a built entry, such as [`Entry::header_offset`], are 0 as it is not read from a
+/// cpio file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntryBuilder<'a>(Entry<'a>);
+impl<'a> EntryBuilder<'a> {
+    /// Creates a new builder of an entry of the given format.
+    ///
+    /// The entry has no content, no mode bits and one link. The other numeric fields are 0.
+    #[must_use]
+    pub fn new(name: &'a str, format: Format) -> Self {
+        let (dev, devnumbers) = match format {
+            Format::OldBinary(_) | Format::PortableAscii => (Some(0), None),
+            Format::NewAscii | Format::NewCrc => (None, Some(0)),
+        };
+
+        Self(Entry {
+            metadata: Metadata {
+                format,
+                dev,
+                devmajor: devnumbers,
+                devminor: devnumbers,
+                ino: 0,
+                mode: Mode::empty(),
+                uid: 0,
+                gid: 0,
+                nlink: 1,
+                rdev: dev,
+                rdevmajor: devnumbers,
+                rdevminor: devnumbers,
+                mtime: 0,
+                check: None,
+                header_offset: 0,
+                name_offset: 0,
+                data_offset: 0,
+                len: 0,
+            },
+            name,
+            file: &[],
+        })
+    }
+
+    /// Sets the device number of the device which contained the file.
+    #[must_use]
+    pub fn dev(mut self, dev: u32) -> Self {
+        self.0.metadata.dev = Some(dev);
+        self
+    }
+
+    /// Sets the major number of the device which contained the file.
+    #[must_use]
+    pub fn devmajor(mut self, devmajor: u32) -> Self {
+        self.0.metadata.devmajor = Some(devmajor);
+        self
+    }
+
+    /// Sets the minor number of the device which contained the file.
+    #[must_use]
+    pub fn devminor(mut self, devminor: u32) -> Self {
+        self.0.metadata.devminor = Some(devminor);
+        self
+    }
+
+    /// Sets the inode number of the file.
+    #[must_use]
+    pub fn ino(mut self, ino: u32) -> Self {
+        self.0.metadata.ino = ino;
+        self
+    }
+
+    /// Sets the [`Mode`] value of the file.
+    #[must_use]
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.0.metadata.mode = mode;
+        self
+    }
+
+    /// Sets the user id of the owner of the file.
+    #[must_use]
+    pub fn uid(mut self, uid: u32) -> Self {
+        self.0.metadata.uid = uid;
+        self
+    }
+
+    /// Sets the group id of the owner of the file.
+    #[must_use]
+    pub fn gid(mut self, gid: u32) -> Self {
+        self.0.metadata.gid = gid;
+        self
+    }
+
+    /// Sets the number of links to this file.
+    #[must_use]
+    pub fn nlink(mut self, nlink: u32) -> Self {
+        self.0.metadata.nlink = nlink;
+        self
+    }
+
+    /// Sets the associated device number of a device file.
+    #[must_use]
+    pub fn rdev(mut self, rdev: u32) -> Self {
+        self.0.metadata.rdev = Some(rdev);
+        self
+    }
+
+    /// Sets the associated device major number of a device file.
+    #[must_use]
+    pub fn rdevmajor(mut self, rdevmajor: u32) -> Self {
+        self.0.metadata.rdevmajor = Some(rdevmajor);
+        self
+    }
+
+    /// Sets the associated device minor number of a device file.
+    #[must_use]
+    pub fn rdevminor(mut self, rdevminor: u32) -> Self {
+        self.0.metadata.rdevminor = Some(rdevminor);
+        self
+    }
+
+    /// Sets the modification time of the file.
+    #[must_use]
+    pub fn mtime(mut self, mtime: u64) -> Self {
+        self.0.metadata.mtime = mtime;
+        self
+    }
+
+    /// Sets the filename.
+    #[must_use]
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.0.name = name;
+        self
+    }
+
+    /// Sets the content of the file.
+    #[must_use]
+    pub fn file(mut self, file: &'a [u8]) -> Self {
+        self.0.file = file;
+        self
+    }
+
+    /// Builds the entry.
+    ///
+    /// If the format is New CRC Format, the checksum of the entry is calculated from the content.
+    #[must_use]
+    pub fn build(mut self) -> Entry<'a> {
+        let file = self.0.file;
+        let metadata = &mut self.0.metadata;
+
+        metadata.check = (metadata.format == Format::NewCrc).then(|| checksum(file));
+
+        metadata.header_offset = 0;
+        metadata.name_offset = 0;
+        metadata.data_offset = 0;
+        metadata.len = 0;
+
+        self.0
+    }
+}
+impl<'a> From<Entry<'a>> for EntryBuilder<'a> {
+    fn from(entry: Entry<'a>) -> Self {
+        Self(entry)
+    }
+}
@@ -0,0 +1,298 @@
+//! The C API, which is enabled by the `capi` feature.
+//!
+//! The functions are declared in `include/cpio_reader.h`. They never allocate memory, so the
+//! caller owns every structure.
+//!
+//! ```c
+//! CpioIter iter;
+//! CpioEntry entry;
+//!
+//! cpio_iter_init(&iter, initramfs, initramfs_len);
+//!
+//! while (cpio_iter_next(&iter, &entry)) {
+//!     size_t name_len;
+//!     const uint8_t *name = cpio_entry_name(&entry, &name_len);
+//!     ...
+//! }
+//! ```
+
+use {
+    crate::{Endianness, Entry, Format, Iter},
+    core::{ptr, slice},
+};
+
+/// An iterator over the entries of a cpio file.
+///
+/// The fields are private. Initialize the iterator with [`cpio_iter_init`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct CpioIter {
+    binary: *const u8,
+    len: usize,
+    offset: usize,
+}
+
+/// An entry of a cpio file.
+///
+/// The pointers point to the cpio file passed to [`cpio_iter_init`], so they are valid as long as
+/// the cpio file is.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct CpioEntry {
+    /// The filename, which is not null-terminated.
+    pub name: *const u8,
+    /// The length of the filename in bytes.
+    pub name_len: usize,
+    /// The content of the file.
+    pub data: *const u8,
+    /// The length of the content in bytes.
+    pub data_len: usize,
+    /// The metadata of the file.
+    pub metadata: CpioMetadata,
+}
+
+/// The format of a cpio file.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CpioFormat {
+    /// Old Binary Format in little endian.
+    OldBinaryLittle,
+    /// Old Binary Format in big endian.
+    OldBinaryBig,
+    /// Portable ASCII Format.
+    PortableAscii,
+    /// New ASCII Format.
+    NewAscii,
+    /// New CRC Format.
+    NewCrc,
+}
+impl From<Format> for CpioFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::OldBinary(Endianness::Little) => Self::OldBinaryLittle,
+            Format::OldBinary(Endianness::Big) => Self::OldBinaryBig,
+            Format::PortableAscii => Self::PortableAscii,
+            Format::NewAscii => Self::NewAscii,
+            Format::NewCrc => Self::NewCrc,
+        }
+    }
+}
+
+/// The metadata of an entry.
+///
+/// The device numbers are split with the Linux encoding for Old Binary Format and Portable ASCII
+/// Format.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CpioMetadata {
+    /// The format of the entry.
+    pub format: CpioFormat,
+    /// The mode as it is stored in the header.
+    pub mode: u32,
+    /// The inode number.
+    pub ino: u32,
+    /// The user ID.
+    pub uid: u32,
+    /// The group ID.
+    pub gid: u32,
+    /// The number of links.
+    pub nlink: u32,
+    /// The modification time in seconds since the Unix epoch.
+    pub mtime: u64,
+    /// The major number of the device which contains the file.
+    pub dev_major: u32,
+    /// The minor number of the device which contains the file.
+    pub dev_minor: u32,
+    /// The major number of the file if it is a special device.
+    pub rdev_major: u32,
+    /// The minor number of the file if it is a special device.
+    pub rdev_minor: u32,
+    /// Whether `check` is stored in the header, which is the case only for New CRC Format.
+    pub has_check: bool,
+    /// The checksum stored in the header.
+    pub check: u32,
+}
+impl From<&Entry<'_>> for CpioMetadata {
+    fn from(entry: &Entry<'_>) -> Self {
+        let device = entry.device();
+        let rdevice = entry.rdevice();
+
+        Self {
+            format: entry.format().into(),
+            mode: entry.raw_mode(),
+            ino: entry.ino(),
+            uid: entry.uid(),
+            gid: entry.gid(),
+            nlink: entry.nlink(),
+            mtime: entry.mtime(),
+            dev_major: device.major,
+            dev_minor: device.minor,
+            rdev_major: rdevice.major,
+            rdev_minor: rdevice.minor,
+            has_check: entry.check().is_some(),
+            check: entry.check().unwrap_or(0),
+        }
+    }
+}
+
+/// Initializes `iter` to iterate over the entries of the cpio file of `len` bytes at `binary`.
+///
+/// This function returns `false` if `iter` is null, or if `binary` is null and `len` is not 0.
+///
+/// # Safety
+///
+/// `iter` must be null or valid for writes. `binary` must be null or valid for reads of `len`
+/// bytes, and the bytes must not be modified while the iterator and the entries are used.
+#[no_mangle]
+pub unsafe extern "C" fn cpio_iter_init(
+    iter: *mut CpioIter,
+    binary: *const u8,
+    len: usize,
+) -> bool {
+    if iter.is_null() || (binary.is_null() && len != 0) {
+        return false;
+    }
+
+    // SAFETY: The caller guarantees that `iter` is valid for writes.
+    unsafe {
+        iter.write(CpioIter {
+            binary,
+            len,
+            offset: 0,
+        });
+    }
+
+    true
+}
+
+/// Reads the next entry into `entry`, and returns `true` if it exists.
+///
+/// This function returns `false` at the end of the cpio file, at a corrupt entry, or if either of
+/// the arguments is null. The function never reads outside of the cpio file.
+///
+/// # Safety
+///
+/// `iter` must be null or a pointer to an iterator initialized by [`cpio_iter_init`]. `entry` must
+/// be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn cpio_iter_next(iter: *mut CpioIter, entry: *mut CpioEntry) -> bool {
+    if iter.is_null() || entry.is_null() {
+        return false;
+    }
+
+    // SAFETY: The caller guarantees that `iter` points to an initialized iterator.
+    let state = unsafe { &mut *iter };
+
+    let binary = if state.binary.is_null() {
+        &[]
+    } else {
+        // SAFETY: `cpio_iter_init` checked the pointer, and the caller guarantees that the
+        // memory is valid for reads of `len` bytes.
+        unsafe { slice::from_raw_parts(state.binary, state.len) }
+    };
+
+    let mut entries = Iter {
+        binary: binary.get(state.offset..).unwrap_or_default(),
+        offset: state.offset,
+    };
+
+    let Some(next) = entries.next() else {
+        return false;
+    };
+
+    state.offset = entries.offset;
+
+    // SAFETY: The caller guarantees that `entry` is valid for writes.
+    unsafe {
+        entry.write(CpioEntry {
+            name: next.name().as_ptr(),
+            name_len: next.name().len(),
+            data: next.file().as_ptr(),
+            data_len: next.file().len(),
+            metadata: (&next).into(),
+        });
+    }
+
+    true
+}
+
+/// Returns the filename of `entry`, which is not null-terminated, and stores its length in
+/// `len`.
+///
+/// This function returns null if `entry` is null. `len` may be null.
+///
+/// # Safety
+///
+/// `entry` must be null or a pointer to an entry read by [`cpio_iter_next`]. `len` must be null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn cpio_entry_name(entry: *const CpioEntry, len: *mut usize) -> *const u8 {
+    // SAFETY: The caller guarantees that the pointers are valid.
+    unsafe { field(entry, len, |entry| (entry.name, entry.name_len)) }
+}
+
+/// Returns the content of `entry`, and stores its length in `len`.
+///
+/// This function returns null if `entry` is null. `len` may be null.
+///
+/// # Safety
+///
+/// `entry` must be null or a pointer to an entry read by [`cpio_iter_next`]. `len` must be null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn cpio_entry_data(entry: *const CpioEntry, len: *mut usize) -> *const u8 {
+    // SAFETY: The caller guarantees that the pointers are valid.
+    unsafe { field(entry, len, |entry| (entry.data, entry.data_len)) }
+}
+
+/// Copies the metadata of `entry` into `metadata`.
+///
+/// This function returns `false` if either of the arguments is null.
+///
+/// # Safety
+///
+/// `entry` must be null or a pointer to an entry read by [`cpio_iter_next`]. `metadata` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn cpio_entry_metadata(
+    entry: *const CpioEntry,
+    metadata: *mut CpioMetadata,
+) -> bool {
+    if entry.is_null() || metadata.is_null() {
+        return false;
+    }
+
+    // SAFETY: The caller guarantees that the pointers are valid.
+    unsafe {
+        metadata.write((*entry).metadata);
+    }
+
+    true
+}
+
+/// Returns a pointer field of `entry` and stores the length in `len`.
+///
+/// # Safety
+///
+/// See [`cpio_entry_name`].
+unsafe fn field(
+    entry: *const CpioEntry,
+    len: *mut usize,
+    field: impl FnOnce(&CpioEntry) -> (*const u8, usize),
+) -> *const u8 {
+    if entry.is_null() {
+        return ptr::null();
+    }
+
+    // SAFETY: The caller guarantees that `entry` points to an entry.
+    let (pointer, field_len) = field(unsafe { &*entry });
+
+    if !len.is_null() {
+        // SAFETY: The caller guarantees that `len` is valid for writes.
+        unsafe {
+            len.write(field_len);
+        }
+    }
+
+    pointer
+}
@@ -0,0 +1,405 @@
+use {
+    crate::{
+        write::{self, Counter, Write, WriteError},
+        Entry, EntryBuilder, Format, HardLinks, Iter, Mode, TRAILER_NAME,
+    },
+    core::fmt,
+};
+
+/// Converts the given cpio file into `format` with the default [`Converter`] and writes it to
+/// `sink`.
+///
+/// # Errors
+///
+/// See [`Converter::convert`].
+pub fn convert<'a, W: Write + ?Sized>(
+    input: &'a [u8],
+    format: Format,
+    sink: &mut W,
+) -> Result<(), ConvertError<'a, W::Error>> {
+    Converter::new(format).convert(input, sink)
+}
+
+/// A converter which converts cpio files into the given format.
+///
+/// Old Binary Format and Portable ASCII Format store a device number in a single field, while New
+/// ASCII Format and New CRC Format store the major and the minor numbers separately. The
+/// converter splits and combines them with [`DeviceEncoding`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Converter {
+    format: Format,
+    device_encoding: DeviceEncoding,
+    block_size: usize,
+}
+impl Converter {
+    /// Creates a new converter into `format`.
+    ///
+    /// The converter uses [`DeviceEncoding::Linux`] and pads the output to a multiple of 512 bytes
+    /// as GNU cpio does.
+    #[must_use]
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            device_encoding: DeviceEncoding::Linux,
+            block_size: 512,
+        }
+    }
+
+    /// Sets the [`Format`] into which cpio files are converted.
+    #[must_use]
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the [`DeviceEncoding`] to split and combine device numbers.
+    #[must_use]
+    pub fn device_encoding(mut self, device_encoding: DeviceEncoding) -> Self {
+        self.device_encoding = device_encoding;
+        self
+    }
+
+    /// Sets the size of the block to which the output is padded with zeros.
+    ///
+    /// The output is not padded if `block_size` is 0 or 1.
+    #[must_use]
+    pub fn block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// Converts the given cpio file and writes it to `sink`.
+    ///
+    /// The content of a file with multiple links is written in every link for Old Binary Format
+    /// and Portable ASCII Format, and only in the last link for New ASCII Format and New CRC
+    /// Format, wherever the input stores it. The contents are looked up with [`HardLinks`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if `input` is corrupt, if `sink` fails, or if an entry cannot
+    /// be represented in the format.
+    pub fn convert<'a, W: Write + ?Sized>(
+        &self,
+        input: &'a [u8],
+        sink: &mut W,
+    ) -> Result<(), ConvertError<'a, W::Error>> {
+        self.convert_inner(input, sink, |entry, field| {
+            Err(ConvertError::Unrepresentable {
+                name: entry.name(),
+                offset: entry.header_offset(),
+                field,
+            })
+        })
+    }
+
+    /// Converts the given cpio file and writes it to `sink`, skipping the entries which cannot be
+    /// represented in the format.
+    ///
+    /// `on_skip` is called with each skipped entry and the name of the header field which cannot
+    /// be represented.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if `input` is corrupt, or if `sink` fails.
+    pub fn convert_skipping<'a, W: Write + ?Sized, F: FnMut(Entry<'a>, &'static str)>(
+        &self,
+        input: &'a [u8],
+        sink: &mut W,
+        mut on_skip: F,
+    ) -> Result<(), ConvertError<'a, W::Error>> {
+        self.convert_inner(input, sink, |entry, field| {
+            on_skip(entry, field);
+
+            Ok(())
+        })
+    }
+
+    /// Converts a single entry and writes it to `sink`.
+    ///
+    /// Use this method with [`write_trailer`](crate::write_trailer) to skip the entries which
+    /// cannot be represented in the format. The content is written as it is, so the contents of
+    /// hard links are not moved as [`Converter::convert`] does.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if `sink` fails, or if the entry cannot be represented in the
+    /// format.
+    pub fn convert_entry<W: Write + ?Sized>(
+        &self,
+        entry: &Entry<'_>,
+        sink: &mut W,
+    ) -> Result<(), WriteError<W::Error>> {
+        let mut converted = *entry;
+        let metadata = &mut converted.metadata;
+
+        match self.format {
+            Format::OldBinary(_) | Format::PortableAscii => {
+                metadata.dev =
+                    Some(self.combine(entry.dev(), entry.devmajor(), entry.devminor(), "dev")?);
+                metadata.rdev = Some(self.combine(
+                    entry.rdev(),
+                    entry.rdevmajor(),
+                    entry.rdevminor(),
+                    "rdev",
+                )?);
+                metadata.devmajor = None;
+                metadata.devminor = None;
+                metadata.rdevmajor = None;
+                metadata.rdevminor = None;
+            }
+            Format::NewAscii | Format::NewCrc => {
+                let device = entry.device_with(self.device_encoding);
+                let rdevice = entry.rdevice_with(self.device_encoding);
+
+                metadata.dev = None;
+                metadata.devmajor = Some(device.major);
+                metadata.devminor = Some(device.minor);
+                metadata.rdev = None;
+                metadata.rdevmajor = Some(rdevice.major);
+                metadata.rdevminor = Some(rdevice.minor);
+            }
+        }
+
+        if self.format != Format::NewCrc || entry.format() != Format::NewCrc {
+            metadata.check = None;
+        }
+
+        metadata.format = self.format;
+
+        write::write_entry(&converted, self.format, sink)
+    }
+
+    fn convert_inner<'a, W: Write + ?Sized>(
+        &self,
+        input: &'a [u8],
+        sink: &mut W,
+        mut on_unrepresentable: impl FnMut(
+            Entry<'a>,
+            &'static str,
+        ) -> Result<(), ConvertError<'a, W::Error>>,
+    ) -> Result<(), ConvertError<'a, W::Error>> {
+        let mut sink = Counter::new(sink);
+        let links = HardLinks::new(input);
+        let mut iter = Iter::new(input);
+
+        for entry in iter.by_ref() {
+            // The writer checks all fields before writing anything, so nothing is written for an
+            // entry which cannot be represented.
+            match self.convert_entry(&self.relink(&entry, &links), &mut sink) {
+                Ok(()) => {}
+                Err(WriteError::Sink(e)) => return Err(ConvertError::Sink(e)),
+                Err(WriteError::Unrepresentable(field)) => on_unrepresentable(entry, field)?,
+            }
+        }
+
+        iter.trailer()
+            .map_err(|offset| ConvertError::Corrupt { offset })?;
+
+        let trailer = EntryBuilder::new(TRAILER_NAME, self.format).build();
+
+        match write::write_entry(&trailer, self.format, &mut sink) {
+            Ok(()) => {}
+            Err(WriteError::Sink(e)) => return Err(ConvertError::Sink(e)),
+            Err(WriteError::Unrepresentable(field)) => on_unrepresentable(trailer, field)?,
+        }
+
+        if self.block_size > 1 {
+            let padding = (self.block_size - sink.written % self.block_size) % self.block_size;
+
+            for _ in 0..padding {
+                sink.write_all(&[0]).map_err(ConvertError::Sink)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves the content of a hard link to where the output format stores it.
+    ///
+    /// Old Binary Format and Portable ASCII Format store the content in every link because GNU cpio
+    /// extracts the later links as links to the first one. New ASCII Format and New CRC Format
+    /// store it only in the last link.
+    fn relink<'a>(&self, entry: &Entry<'a>, links: &HardLinks<'a>) -> Entry<'a> {
+        if entry.mode().file_type() != Mode::REGULAR_FILE || entry.nlink() <= 1 {
+            return *entry;
+        }
+
+        let file = match self.format {
+            Format::OldBinary(_) | Format::PortableAscii => links.content(entry),
+            Format::NewAscii | Format::NewCrc if links.is_last(entry) => links.content(entry),
+            Format::NewAscii | Format::NewCrc => &[],
+        };
+
+        let mut relinked = Entry { file, ..*entry };
+
+        // The stored checksum is of the original content.
+        if file.len() != entry.file().len() {
+            relinked.metadata.check = None;
+        }
+
+        relinked
+    }
+
+    fn combine<E>(
+        self,
+        dev: Option<u32>,
+        major: Option<u32>,
+        minor: Option<u32>,
+        field: &'static str,
+    ) -> Result<u32, WriteError<E>> {
+        match dev {
+            Some(dev) => Ok(dev),
+            None => self
+                .device_encoding
+                .encode(major.unwrap_or(0), minor.unwrap_or(0))
+                .ok_or(WriteError::Unrepresentable(field)),
+        }
+    }
+}
+
+/// The encoding of a device number which consists of the major and the minor numbers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum DeviceEncoding {
+    /// The encoding of `dev_t` used by Linux and glibc.
+    ///
+    /// The bits 0-7 and 20-31 represent the minor number, and the bits 8-19 represent the major
+    /// number. This is compatible with the traditional encoding which stores the major number in
+    /// the upper 8 bits and the minor number in the lower 8 bits of a 16-bit value.
+    #[default]
+    Linux,
+    /// The encoding which stores the minor number in the lower `minor_bits` bits and the major
+    /// number in the remaining upper bits.
+    Split {
+        /// The number of bits for the minor number.
+        minor_bits: u32,
+    },
+}
+impl DeviceEncoding {
+    /// Splits the given device number into the major and the minor numbers.
+    #[must_use]
+    pub fn decode(self, dev: u32) -> (u32, u32) {
+        match self {
+            Self::Linux => ((dev >> 8) & 0xfff, (dev & 0xff) | ((dev >> 12) & 0xf_ff00)),
+            Self::Split { minor_bits } => (
+                dev.checked_shr(minor_bits).unwrap_or(0),
+                dev & low_bits_mask(minor_bits),
+            ),
+        }
+    }
+
+    /// Combines the given major and minor numbers into a device number.
+    ///
+    /// This method returns [`None`] if either of the numbers does not fit in the encoding.
+    #[must_use]
+    pub fn encode(self, major: u32, minor: u32) -> Option<u32> {
+        match self {
+            Self::Linux => (major <= 0xfff && minor <= 0xf_ffff)
+                .then_some((minor & 0xff) | (major << 8) | ((minor & !0xff) << 12)),
+            Self::Split { minor_bits } => {
+                let major_fits = major == 0 || u32::MAX.checked_shr(minor_bits) >= Some(major);
+                let minor_fits = minor & !low_bits_mask(minor_bits) == 0;
+
+                (major_fits && minor_fits)
+                    .then(|| major.checked_shl(minor_bits).unwrap_or(0) | minor)
+            }
+        }
+    }
+}
+
+/// A device number which consists of the major and the minor numbers.
+///
+/// This type is displayed as `major:minor`, such as `8:2`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DeviceNumber {
+    /// The major number.
+    pub major: u32,
+    /// The minor number.
+    pub minor: u32,
+}
+impl DeviceNumber {
+    /// Creates a new device number.
+    #[must_use]
+    pub fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Returns the device number stored in either the single field or the pair of the fields of
+    /// a header, splitting the single field with `encoding`.
+    pub(crate) fn from_fields(
+        encoding: DeviceEncoding,
+        dev: Option<u32>,
+        major: Option<u32>,
+        minor: Option<u32>,
+    ) -> Self {
+        match (major, minor) {
+            (Some(major), Some(minor)) => Self { major, minor },
+            _ => encoding.decode(dev.unwrap_or(0)).into(),
+        }
+    }
+}
+impl From<(u32, u32)> for DeviceNumber {
+    fn from((major, minor): (u32, u32)) -> Self {
+        Self { major, minor }
+    }
+}
+impl From<DeviceNumber> for (u32, u32) {
+    fn from(device: DeviceNumber) -> Self {
+        (device.major, device.minor)
+    }
+}
+impl fmt::Display for DeviceNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.major, self.minor)
+    }
+}
+
+/// An error which occurs while converting a cpio file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ConvertError<'a, E> {
+    /// The input is corrupt at the given offset.
+    Corrupt {
+        /// The offset of the corrupt entry from the start of the input.
+        offset: usize,
+    },
+    /// The sink returned an error.
+    Sink(E),
+    /// The entry cannot be represented in the format.
+    Unrepresentable {
+        /// The name of the entry which cannot be represented.
+        name: &'a str,
+        /// The offset of the header of the entry from the start of the input.
+        offset: usize,
+        /// The name of the header field which cannot be represented.
+        field: &'static str,
+    },
+}
+impl<E: fmt::Display> fmt::Display for ConvertError<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Corrupt { offset } => write!(f, "the cpio file is corrupt at offset {offset}"),
+            Self::Sink(e) => write!(f, "failed to write to the sink: {e}"),
+            Self::Unrepresentable { name, field, .. } => {
+                write!(
+                    f,
+                    "the value of `{field}` of `{name}` cannot be represented"
+                )
+            }
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ConvertError<'_, E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Sink(e) => Some(e),
+            Self::Corrupt { .. } | Self::Unrepresentable { .. } => None,
+        }
+    }
+}
+
+fn low_bits_mask(bits: u32) -> u32 {
+    1_u32.checked_shl(bits).map_or(u32::MAX, |bit| bit - 1)
+}
@@ -0,0 +1,157 @@
+use {
+    crate::{links::HardLinks, Entry, EntryPath, Iter},
+    alloc::{collections::BTreeMap, vec::Vec},
+    bitflags::bitflags,
+    core::fmt::{self, Write},
+};
+
+/// Compares two cpio files and returns the differences between their entries.
+///
+/// The entries are matched by their [`EntryPath`]s, so `./bin/sh` in `old` is compared with
+/// `bin/sh` in `new`. If multiple entries have the same path, the last one is used as extracting
+/// the cpio file leaves it. The differences are sorted by the paths. The entries after a corrupt
+/// one are ignored.
+///
+/// Inode numbers, link counts and device numbers of the containing devices are not compared
+/// because they usually differ between cpio files of the same tree. The hard links without
+/// contents in New ASCII Format and New CRC Format are compared with the content stored in
+/// another link to the same file.
+#[must_use]
+pub fn diff<'a>(old: &'a [u8], new: &'a [u8]) -> Vec<Difference<'a>> {
+    let mut old = entries(old);
+    let mut differences = Vec::new();
+
+    for (path, (new, new_file)) in entries(new) {
+        match old.remove(&path) {
+            Some((old, old_file)) => {
+                let changes = Changes::compare(&old, old_file, &new, new_file);
+
+                if !changes.is_empty() {
+                    differences.push(Difference::Changed { old, new, changes });
+                }
+            }
+            None => differences.push(Difference::Added(new)),
+        }
+    }
+
+    differences.extend(old.into_values().map(|(old, _)| Difference::Removed(old)));
+    differences.sort_by_key(Difference::path);
+
+    differences
+}
+
+/// A difference between two cpio files.
+///
+/// This type is displayed as `+ path` for an added entry, `- path` for a removed one, and
+/// `~ path: changes` for a changed one, where `changes` is the list of the [`Changes`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Difference<'a> {
+    /// The entry exists only in the new cpio file.
+    Added(Entry<'a>),
+    /// The entry exists only in the old cpio file.
+    Removed(Entry<'a>),
+    /// The entry exists in both cpio files, and they differ.
+    Changed {
+        /// The entry in the old cpio file.
+        old: Entry<'a>,
+        /// The entry in the new cpio file.
+        new: Entry<'a>,
+        /// The changed properties.
+        changes: Changes,
+    },
+}
+impl<'a> Difference<'a> {
+    /// Returns the path of the entry.
+    #[must_use]
+    pub fn path(&self) -> EntryPath<'a> {
+        match self {
+            Self::Added(entry) | Self::Removed(entry) | Self::Changed { new: entry, .. } => {
+                entry.path()
+            }
+        }
+    }
+}
+impl fmt::Display for Difference<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added(entry) => write!(f, "+ {}", entry.path()),
+            Self::Removed(entry) => write!(f, "- {}", entry.path()),
+            Self::Changed { new, changes, .. } => write!(f, "~ {}: {}", new.path(), changes),
+        }
+    }
+}
+
+bitflags! {
+    /// The properties of an entry which differ between two cpio files.
+    ///
+    /// This type is displayed as the comma-separated list of the names of the properties, such as
+    /// `content, mode`.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Changes: u8 {
+        /// The content, which is the target for a symbolic link.
+        const CONTENT = 0b0000_0001;
+        /// The mode, including the file type.
+        const MODE = 0b0000_0010;
+        /// The user ID.
+        const UID = 0b0000_0100;
+        /// The group ID.
+        const GID = 0b0000_1000;
+        /// The modification time.
+        const MTIME = 0b0001_0000;
+        /// The device number of a character or block special device.
+        const RDEV = 0b0010_0000;
+    }
+}
+impl Changes {
+    /// Returns the properties which differ between `old` and `new`.
+    ///
+    /// The device numbers are compared with [`Entry::rdevice`], so they are compared correctly
+    /// between different formats.
+    #[must_use]
+    pub fn between(old: &Entry<'_>, new: &Entry<'_>) -> Self {
+        Self::compare(old, old.file(), new, new.file())
+    }
+
+    /// Returns `true` if only the metadata changed, keeping the content.
+    #[must_use]
+    pub fn is_metadata_only(self) -> bool {
+        !self.is_empty() && !self.contains(Self::CONTENT)
+    }
+
+    fn compare(old: &Entry<'_>, old_file: &[u8], new: &Entry<'_>, new_file: &[u8]) -> Self {
+        let mut changes = Self::empty();
+
+        changes.set(Self::CONTENT, old_file != new_file);
+        changes.set(Self::MODE, old.mode() != new.mode());
+        changes.set(Self::UID, old.uid() != new.uid());
+        changes.set(Self::GID, old.gid() != new.gid());
+        changes.set(Self::MTIME, old.mtime() != new.mtime());
+        changes.set(Self::RDEV, old.rdevice() != new.rdevice());
+
+        changes
+    }
+}
+impl fmt::Display for Changes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, _)) in self.iter_names().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            name.chars()
+                .try_for_each(|c| f.write_char(c.to_ascii_lowercase()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the entries by their paths with their contents, looking up the contents of hard links
+/// stored in other entries.
+fn entries(cpio_binary: &[u8]) -> BTreeMap<EntryPath<'_>, (Entry<'_>, &[u8])> {
+    let links = HardLinks::new(cpio_binary);
+
+    Iter::new(cpio_binary)
+        .map(|entry| (entry.path(), (entry, links.content(&entry))))
+        .collect()
+}
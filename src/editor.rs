@@ -0,0 +1,189 @@
+use {
+    crate::{
+        write::{self, Counter, Write, WriteError},
+        Converter, Entry, EntryBuilder, EntryPath, Format, Iter, Mode,
+    },
+    alloc::vec::Vec,
+};
+
+/// An editor of a cpio file, which removes, replaces and appends entries.
+///
+/// The edited cpio file is written in the format of the original one. The entries which are not
+/// modified are written byte-identically, followed by a new trailer and zeros padding the output
+/// to a multiple of 512 bytes as GNU cpio does. Any data following the original trailer is
+/// dropped.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ArchiveEditor<'a> {
+    archive: &'a [u8],
+    format: Format,
+    entries: Vec<Edit<'a>>,
+}
+impl<'a> ArchiveEditor<'a> {
+    /// Reads the given cpio file to edit.
+    ///
+    /// This method returns [`None`] if the cpio file is corrupt, or if it contains neither an
+    /// entry nor a trailer so that the format cannot be determined.
+    #[must_use]
+    pub fn new(archive: &'a [u8]) -> Option<Self> {
+        let mut iter = Iter::new(archive);
+
+        let entries: Vec<_> = iter.by_ref().map(Edit::Untouched).collect();
+
+        let trailer = iter.trailer().ok()?;
+
+        let format = entries
+            .first()
+            .map(|edit| edit.entry().format())
+            .or_else(|| trailer.map(|trailer| trailer.format()))?;
+
+        Some(Self {
+            archive,
+            format,
+            entries,
+        })
+    }
+
+    /// Returns the [`Format`] in which the edited cpio file is written.
+    #[must_use]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns an iterator over the entries after editing.
+    pub fn entries(&self) -> impl Iterator<Item = Entry<'a>> + '_ {
+        self.entries.iter().map(Edit::entry)
+    }
+
+    /// Returns the first entry whose path is `name`.
+    ///
+    /// The paths are compared as [`EntryPath`]s, so `usr/bin/sh` also finds the entries named
+    /// `./usr/bin/sh` and `/usr/bin/sh`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Entry<'a>> {
+        self.position(name).map(|i| self.entries[i].entry())
+    }
+
+    /// Removes the first entry whose path is `name`, and returns it.
+    ///
+    /// New ASCII Format and New CRC Format store the content of a file with multiple links in only
+    /// its last link. If that link is removed, its content is moved to the last of the remaining
+    /// links.
+    pub fn remove(&mut self, name: &str) -> Option<Entry<'a>> {
+        let removed = self.entries.remove(self.position(name)?).entry();
+
+        if matches!(self.format, Format::NewAscii | Format::NewCrc)
+            && removed.mode().file_type() == Mode::REGULAR_FILE
+            && removed.nlink() > 1
+            && !removed.file().is_empty()
+        {
+            let last_link = self.entries.iter_mut().rev().find(|edit| {
+                let entry = edit.entry();
+
+                entry.nlink() > 1
+                    && entry.ino() == removed.ino()
+                    && entry.device() == removed.device()
+            });
+
+            if let Some(last_link) = last_link {
+                let entry = last_link.entry();
+
+                if entry.file().is_empty() {
+                    *last_link =
+                        Edit::Modified(EntryBuilder::from(entry).file(removed.file()).build());
+                }
+            }
+        }
+
+        Some(removed)
+    }
+
+    /// Replaces the first entry whose path is `name` with `entry`, and returns the replaced one.
+    ///
+    /// `entry` is written in the format of the edited cpio file regardless of
+    /// [`Entry::format`], converting the device numbers with [`Converter`].
+    pub fn replace(&mut self, name: &str, entry: Entry<'a>) -> Option<Entry<'a>> {
+        let i = self.position(name)?;
+
+        Some(core::mem::replace(&mut self.entries[i], Edit::Modified(entry)).entry())
+    }
+
+    /// Replaces the content of the first entry whose path is `name` with `file`, and returns the
+    /// replaced entry.
+    pub fn replace_file(&mut self, name: &str, file: &'a [u8]) -> Option<Entry<'a>> {
+        let entry = self.get(name)?;
+
+        self.replace(name, EntryBuilder::from(entry).file(file).build())
+    }
+
+    /// Appends `entry` to the end of the cpio file.
+    ///
+    /// `entry` is written in the format of the edited cpio file regardless of
+    /// [`Entry::format`], converting the device numbers with [`Converter`].
+    pub fn append(&mut self, entry: Entry<'a>) {
+        self.entries.push(Edit::Modified(entry));
+    }
+
+    /// Writes the edited cpio file to `sink`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if `sink` fails, or if a modified entry cannot be represented
+    /// in the format, such as a device number which does not fit in
+    /// [`DeviceEncoding::Linux`](crate::DeviceEncoding::Linux).
+    pub fn write_to<W: Write + ?Sized>(&self, sink: &mut W) -> Result<(), WriteError<W::Error>> {
+        let mut sink = Counter::new(sink);
+
+        for edit in &self.entries {
+            match edit {
+                Edit::Untouched(entry) => {
+                    let start = entry.header_offset();
+                    let end = start + entry.entry_len();
+
+                    let bytes = &self.archive[start..end.min(self.archive.len())];
+
+                    sink.write_all(bytes)?;
+
+                    // The padding of the last entry may be omitted in the original file.
+                    for _ in bytes.len()..entry.entry_len() {
+                        sink.write_all(&[0])?;
+                    }
+                }
+                Edit::Modified(entry) => {
+                    Converter::new(self.format).convert_entry(entry, &mut sink)?;
+                }
+            }
+        }
+
+        write::write_trailer(self.format, &mut sink)?;
+
+        let padding = (BLOCK_SIZE - sink.written % BLOCK_SIZE) % BLOCK_SIZE;
+
+        for _ in 0..padding {
+            sink.write_all(&[0])?;
+        }
+
+        Ok(())
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|edit| edit.entry().path() == EntryPath::new(name))
+    }
+}
+
+/// The size of the block to which the edited cpio file is padded with zeros.
+const BLOCK_SIZE: usize = 512;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Edit<'a> {
+    Untouched(Entry<'a>),
+    Modified(Entry<'a>),
+}
+impl<'a> Edit<'a> {
+    fn entry(&self) -> Entry<'a> {
+        match self {
+            Self::Untouched(entry) | Self::Modified(entry) => *entry,
+        }
+    }
+}
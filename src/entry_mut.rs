@@ -0,0 +1,149 @@
+use {
+    crate::{checksum, write, Entry, Metadata},
+    core::{mem, str},
+};
+
+/// The offset of the `check` field from the start of the header of New CRC Format.
+const CHECK_FIELD_OFFSET: usize = 6 + 8 * 12;
+/// The length of the `check` field of New CRC Format.
+const CHECK_FIELD_LEN: usize = 8;
+
+/// Returns an iterator that iterates over each content of the given cpio file, allowing the
+/// contents of the files to be modified in place.
+///
+/// The iterator checks the headers in the same way as [`iter_files`](crate::iter_files), and never
+/// panics either. Note that modifying the content of an entry of New CRC Format invalidates its
+/// checksum, and the entry will no longer be read by the iterators unless
+/// [`EntryMut::update_check`] is called.
+pub fn iter_files_mut(cpio_binary: &mut [u8]) -> impl Iterator<Item = EntryMut<'_>> {
+    IterMut::new(cpio_binary)
+}
+
+/// An entry of a cpio file whose content can be modified in place.
+///
+/// The length of the content cannot be changed because it is recorded in the header.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntryMut<'a> {
+    metadata: Metadata,
+    name: &'a str,
+    file: &'a mut [u8],
+    check_field: Option<&'a mut [u8]>,
+}
+impl<'a> EntryMut<'a> {
+    /// Returns an [`Entry`] which describes this entry.
+    ///
+    /// The returned value reflects the modifications made to the content so far.
+    #[must_use]
+    pub fn entry(&self) -> Entry<'_> {
+        Entry {
+            metadata: self.metadata,
+            name: self.name,
+            file: self.file,
+        }
+    }
+
+    /// Returns the filename.
+    #[must_use]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the content of this file.
+    #[must_use]
+    pub fn file(&self) -> &[u8] {
+        self.file
+    }
+
+    /// Returns the mutable reference to the content of this file.
+    #[must_use]
+    pub fn file_mut(&mut self) -> &mut [u8] {
+        self.file
+    }
+
+    /// Converts this entry into the mutable reference to the content, which is alive as long as
+    /// the underlying data.
+    #[must_use]
+    pub fn into_file_mut(self) -> &'a mut [u8] {
+        self.file
+    }
+
+    /// Recomputes the checksum of the content and writes it to the header.
+    ///
+    /// This method must be called after modifying the content of an entry of New CRC Format.
+    /// It returns `false` without doing anything if the entry format is not New CRC Format.
+    pub fn update_check(&mut self) -> bool {
+        if let Some(check_field) = &mut self.check_field {
+            let check = checksum(self.file);
+
+            // A `u32` value always fits in the 8 hexadecimal digits.
+            let _ = write::write_digits::<()>(check_field, check.into(), 16, "check");
+
+            self.metadata.check = Some(check);
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+struct IterMut<'a> {
+    binary: &'a mut [u8],
+    offset: usize,
+}
+impl<'a> IterMut<'a> {
+    fn new(binary: &'a mut [u8]) -> Self {
+        Self { binary, offset: 0 }
+    }
+}
+impl<'a> Iterator for IterMut<'a> {
+    type Item = EntryMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.binary.is_empty() {
+            return None;
+        }
+
+        let (metadata, name_len, file_len) = {
+            let (entry, _) = Entry::new(self.binary, self.offset)?;
+
+            (entry.metadata, entry.name.len(), entry.file.len())
+        };
+
+        let head_len = metadata.data_offset - metadata.header_offset;
+        let padding_len = metadata.len - head_len - file_len;
+
+        let binary = mem::take(&mut self.binary);
+
+        // The padding after the name may be missing if the entry has no content and is at the end
+        // of the cpio file.
+        let (head, remaining) = binary.split_at_mut(head_len.min(binary.len()));
+        let (file, remaining) = remaining.split_at_mut(file_len);
+
+        self.binary = remaining.get_mut(padding_len..).unwrap_or_default();
+        self.offset += metadata.len;
+
+        let (check_field, head, head_start) = if metadata.check.is_some() {
+            let (_, check_field_and_remaining) = head.split_at_mut(CHECK_FIELD_OFFSET);
+            let (check_field, remaining) = check_field_and_remaining.split_at_mut(CHECK_FIELD_LEN);
+
+            (
+                Some(check_field),
+                remaining,
+                CHECK_FIELD_OFFSET + CHECK_FIELD_LEN,
+            )
+        } else {
+            (None, head, 0)
+        };
+
+        let name_start = metadata.name_offset - metadata.header_offset - head_start;
+        let name = str::from_utf8(&head[name_start..name_start + name_len]).ok()?;
+
+        Some(EntryMut {
+            metadata,
+            name,
+            file,
+            check_field,
+        })
+    }
+}
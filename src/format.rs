@@ -0,0 +1,51 @@
+use {
+    crate::{Endianness, Format},
+    core::{fmt, str::FromStr},
+};
+
+/// The names of the formats, and the corresponding formats.
+const NAMES: [(&str, Format); 5] = [
+    ("bin", Format::OldBinary(Endianness::Little)),
+    ("bin-be", Format::OldBinary(Endianness::Big)),
+    ("odc", Format::PortableAscii),
+    ("newc", Format::NewAscii),
+    ("crc", Format::NewCrc),
+];
+
+impl fmt::Display for Format {
+    /// Displays the name of the format which the `-H` option of GNU cpio takes, such as `newc`.
+    ///
+    /// Old Binary Format in big endian is shown as `bin-be`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, _) = NAMES
+            .iter()
+            .find(|(_, format)| format == self)
+            .ok_or(fmt::Error)?;
+
+        f.write_str(name)
+    }
+}
+impl FromStr for Format {
+    type Err = ParseFormatError;
+
+    /// Parses the name of a format, which is one of `bin`, `bin-be`, `odc`, `newc` and `crc`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NAMES
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|(_, format)| *format)
+            .ok_or(ParseFormatError)
+    }
+}
+
+/// An error which occurs while parsing a [`Format`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParseFormatError;
+impl fmt::Display for ParseFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown format")
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for ParseFormatError {}
@@ -2,15 +2,87 @@
 #![no_std]
 #![deny(unsafe_code)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod builder;
+#[cfg(feature = "capi")]
+#[allow(unsafe_code)]
+pub mod capi;
+mod convert;
+#[cfg(feature = "alloc")]
+mod diff;
+#[cfg(feature = "alloc")]
+mod editor;
+mod entry_mut;
+mod format;
+mod limits;
+mod links;
+#[cfg(feature = "digest")]
+mod manifest;
+mod mode;
+mod path;
+#[cfg(feature = "rpm")]
+mod rpm;
+#[cfg(feature = "serde")]
+mod ser;
+mod signature;
+#[cfg(feature = "alloc")]
+mod symlink;
+#[cfg(feature = "tar")]
+mod tar_bridge;
+mod timestamp;
+mod write;
+
+#[cfg(feature = "alloc")]
+pub use diff::{diff, Changes, Difference};
+#[cfg(feature = "alloc")]
+pub use editor::ArchiveEditor;
+#[cfg(feature = "digest")]
+pub use manifest::Manifest;
+#[cfg(feature = "rpm")]
+pub use rpm::{RpmError, RpmPayload};
+#[cfg(feature = "serde")]
+pub use ser::WithContent;
+#[cfg(feature = "alloc")]
+pub use symlink::{ResolveError, SymlinkResolver};
+#[cfg(feature = "tar")]
+pub use tar_bridge::{cpio_to_tar, tar_to_cpio};
+#[cfg(feature = "std")]
+pub use write::IoWriter;
+pub use {
+    builder::EntryBuilder,
+    convert::{convert, ConvertError, Converter, DeviceEncoding, DeviceNumber},
+    entry_mut::{iter_files_mut, EntryMut},
+    format::ParseFormatError,
+    limits::{LimitError, ParseLimits},
+    links::HardLinks,
+    mode::ParseModeError,
+    path::{Component, EntryPath},
+    signature::{AppendedSignature, SignatureError, VerifiedArchive},
+    timestamp::Timestamp,
+    write::{write_trailer, Write, WriteError},
+};
+
 use {
     bitflags::bitflags,
     core::{convert::TryInto, str},
 };
 
+const TRAILER_NAME: &str = "TRAILER!!!";
+/// The mask of the bits of [`Mode`] which represent the file type.
+const FILE_TYPE_MASK: u32 = 0o170_000;
+/// The mask of the bits of [`Mode`] which represent the permissions.
+#[cfg(feature = "tar")]
+const PERMISSIONS_MASK: u32 = 0o7777;
+
 /// Returns an iterator that iterates over each content of the given cpio file.
 ///
 /// The iterator checks if the header of an entry is correct. If it is corrupt (e.g., wrong magic
-/// value), the iterator stops iterating.
+/// value), the iterator stops iterating. The iterator never panics whatever `cpio_binary`
+/// contains.
 pub fn iter_files(cpio_binary: &[u8]) -> impl Iterator<Item = Entry<'_>> {
     Iter::new(cpio_binary)
 }
@@ -18,18 +90,7 @@ pub fn iter_files(cpio_binary: &[u8]) -> impl Iterator<Item = Entry<'_>> {
 /// An entry of a cpio file.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Entry<'a> {
-    dev: Option<u32>,
-    devmajor: Option<u32>,
-    devminor: Option<u32>,
-    ino: u32,
-    mode: Mode,
-    uid: u32,
-    gid: u32,
-    nlink: u32,
-    rdev: Option<u32>,
-    rdevmajor: Option<u32>,
-    rdevminor: Option<u32>,
-    mtime: u64,
+    metadata: Metadata,
     name: &'a str,
     file: &'a [u8],
 }
@@ -40,7 +101,7 @@ impl<'a> Entry<'a> {
     /// Format. For these formats, use [`Entry::devmajor`] and [`Entry::devminor`].
     #[must_use]
     pub fn dev(&self) -> Option<u32> {
-        self.dev
+        self.metadata.dev
     }
 
     /// Returns the major number of the device which contained the file.
@@ -49,7 +110,7 @@ impl<'a> Entry<'a> {
     /// ASCII Format. For these formats, use [`Entry::dev`].
     #[must_use]
     pub fn devmajor(&self) -> Option<u32> {
-        self.devmajor
+        self.metadata.devmajor
     }
 
     /// Returns the minor number of the device which contained the file.
@@ -58,38 +119,49 @@ impl<'a> Entry<'a> {
     /// ASCII Format. For these formats, use [`Entry::dev`].
     #[must_use]
     pub fn devminor(&self) -> Option<u32> {
-        self.devminor
+        self.metadata.devminor
     }
 
     /// Returns the inode number of the file.
     #[must_use]
     pub fn ino(&self) -> u32 {
-        self.ino
+        self.metadata.ino
     }
 
     /// Returns the [`Mode`] value of the file, which contains the file's permission information
     /// and file type.
+    ///
+    /// The bits which are not declared in [`Mode`] are retained, such as the bits beyond
+    /// `0o177777` and unknown file types like whiteouts (`0o160000`).
     #[must_use]
     pub fn mode(&self) -> Mode {
-        self.mode
+        self.metadata.mode
+    }
+
+    /// Returns the mode of the file as it is stored in the header.
+    ///
+    /// This is the same as `entry.mode().bits()`.
+    #[must_use]
+    pub fn raw_mode(&self) -> u32 {
+        self.metadata.mode.bits()
     }
 
     /// Returns the user id of the owner of the file.
     #[must_use]
     pub fn uid(&self) -> u32 {
-        self.uid
+        self.metadata.uid
     }
 
     /// Returns the group id of the owner of the file.
     #[must_use]
     pub fn gid(&self) -> u32 {
-        self.gid
+        self.metadata.gid
     }
 
     /// Returns the number of links to this file.
     #[must_use]
     pub fn nlink(&self) -> u32 {
-        self.nlink
+        self.metadata.nlink
     }
 
     /// Returns the associated device number if the entry is block special device or character
@@ -99,7 +171,7 @@ impl<'a> Entry<'a> {
     /// Format. For these formats, use [`Entry::rdevmajor`] and [`Entry::rdevminor`].
     #[must_use]
     pub fn rdev(&self) -> Option<u32> {
-        self.rdev
+        self.metadata.rdev
     }
 
     /// Returns the associated device major number if the entry is block special device or
@@ -110,7 +182,7 @@ impl<'a> Entry<'a> {
     /// ASCII Format. For these formats, use [`Entry::rdev`].
     #[must_use]
     pub fn rdevmajor(&self) -> Option<u32> {
-        self.rdevmajor
+        self.metadata.rdevmajor
     }
 
     /// Returns the associated device minor number if the entry is block special device or
@@ -121,13 +193,90 @@ impl<'a> Entry<'a> {
     /// ASCII Format. For these formats, use [`Entry::rdev`].
     #[must_use]
     pub fn rdevminor(&self) -> Option<u32> {
-        self.rdevminor
+        self.metadata.rdevminor
+    }
+
+    /// Returns the number of the device which contains this file.
+    ///
+    /// This method works for all formats. The device number of Old Binary Format and Portable
+    /// ASCII Format is split with [`DeviceEncoding::Linux`]. Use [`Entry::device_with`] for other
+    /// encodings.
+    #[must_use]
+    pub fn device(&self) -> DeviceNumber {
+        self.device_with(DeviceEncoding::Linux)
+    }
+
+    /// Returns the number of the device which contains this file, splitting the device number of
+    /// Old Binary Format and Portable ASCII Format with `encoding`.
+    #[must_use]
+    pub fn device_with(&self, encoding: DeviceEncoding) -> DeviceNumber {
+        DeviceNumber::from_fields(encoding, self.dev(), self.devmajor(), self.devminor())
+    }
+
+    /// Returns the device number of this file if it is a character or block special device.
+    ///
+    /// This method works for all formats. The device number of Old Binary Format and Portable
+    /// ASCII Format is split with [`DeviceEncoding::Linux`]. Use [`Entry::rdevice_with`] for
+    /// other encodings.
+    #[must_use]
+    pub fn rdevice(&self) -> DeviceNumber {
+        self.rdevice_with(DeviceEncoding::Linux)
+    }
+
+    /// Returns the device number of this file if it is a character or block special device,
+    /// splitting the device number of Old Binary Format and Portable ASCII Format with
+    /// `encoding`.
+    #[must_use]
+    pub fn rdevice_with(&self, encoding: DeviceEncoding) -> DeviceNumber {
+        DeviceNumber::from_fields(encoding, self.rdev(), self.rdevmajor(), self.rdevminor())
     }
 
     /// Returns the modification time of this file.
     #[must_use]
     pub fn mtime(&self) -> u64 {
-        self.mtime
+        self.metadata.mtime
+    }
+
+    /// Returns the modification time of this file as a [`Timestamp`].
+    #[must_use]
+    pub fn modified(&self) -> Timestamp {
+        Timestamp::from_secs(self.metadata.mtime)
+    }
+
+    /// Returns the modification time of this file, assuming that it is the time closest to
+    /// `reference` among those whose lowest bits are stored in the header.
+    ///
+    /// Old Binary Format, New ASCII Format and New CRC Format store the modification time in 32
+    /// bits, which overflow in 2106, and Portable ASCII Format stores it in 33 bits. Passing the
+    /// current time as `reference` recovers the times written by the archivers which store only
+    /// the lowest bits of later times. See [`Timestamp::nearest_wrapped`].
+    #[must_use]
+    pub fn modified_near(&self, reference: Timestamp) -> Timestamp {
+        let bits = match self.format() {
+            Format::OldBinary(_) | Format::NewAscii | Format::NewCrc => 32,
+            Format::PortableAscii => 33,
+        };
+
+        self.modified().nearest_wrapped(bits, reference)
+    }
+
+    /// Returns the checksum stored in the header of this entry.
+    ///
+    /// This method returns [`None`] if the entry format is not New CRC Format. Note that the
+    /// checksum of a symbolic link is not verified by the iterator, following GNU cpio.
+    #[must_use]
+    pub fn check(&self) -> Option<u32> {
+        self.metadata.check
+    }
+
+    /// Returns the checksum computed from the content of this file.
+    ///
+    /// The checksum is the sum of all bytes of [`Entry::file`], wrapping on overflow. This is the
+    /// value which New CRC Format stores in the header, and it can be calculated for entries of
+    /// any format.
+    #[must_use]
+    pub fn computed_checksum(&self) -> u32 {
+        checksum(self.file)
     }
 
     /// Returns the filename.
@@ -136,6 +285,12 @@ impl<'a> Entry<'a> {
         self.name
     }
 
+    /// Returns the filename as an [`EntryPath`], which compares the filenames component-wise.
+    #[must_use]
+    pub fn path(&self) -> EntryPath<'a> {
+        EntryPath::new(self.name)
+    }
+
     /// Returns the content of this file.
     ///
     /// This method returns the path to the original file if the file is a symbolic link. For the
@@ -146,10 +301,96 @@ impl<'a> Entry<'a> {
         self.file
     }
 
-    fn interpret_as_old_binary(binary: &'a [u8]) -> Option<(Self, &'a [u8])> {
+    /// Returns the target of this file if it is a symbolic link.
+    ///
+    /// This method returns [`None`] if the file is not a symbolic link, or if the target is not
+    /// valid UTF-8.
+    #[must_use]
+    pub fn symlink_target(&self) -> Option<&'a str> {
+        if self.mode().bits() & FILE_TYPE_MASK == Mode::SYMBOLIK_LINK.bits() {
+            str::from_utf8(self.file).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Returns a wrapper of this entry which is serialized with the content encoded in Base64.
+    ///
+    /// The entry itself is serialized without the content.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn with_content(&self) -> WithContent<'a> {
+        WithContent(*self)
+    }
+
+    /// Returns the digest of the content of this file computed with `D`.
+    ///
+    /// Note that a hard link in New ASCII Format and New CRC Format may have no content. See
+    /// [`Entry::file`].
+    #[cfg(feature = "digest")]
+    #[must_use]
+    pub fn digest<D: digest::Digest>(&self) -> digest::Output<D> {
+        D::digest(self.file)
+    }
+
+    /// Returns the size of the content of this file in bytes.
+    ///
+    /// This is the length of [`Entry::file`] as a `u64`, which does not depend on the pointer
+    /// width of the target.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.file.len().try_into().unwrap_or(u64::MAX)
+    }
+
+    /// Returns the [`Format`] of this entry.
+    #[must_use]
+    pub fn format(&self) -> Format {
+        self.metadata.format
+    }
+
+    /// Returns the offset of the header of this entry from the start of the cpio file.
+    #[must_use]
+    pub fn header_offset(&self) -> usize {
+        self.metadata.header_offset
+    }
+
+    /// Returns the offset of the content of this file from the start of the cpio file.
+    ///
+    /// The content returned by [`Entry::file`] is located at
+    /// `data_offset()..data_offset() + file().len()` of the cpio file.
+    #[must_use]
+    pub fn data_offset(&self) -> usize {
+        self.metadata.data_offset
+    }
+
+    /// Returns the length of this entry in bytes, including the header, the filename, the content
+    /// and the padding.
+    ///
+    /// The header of the next entry is located at `header_offset() + entry_len()`.
+    #[must_use]
+    pub fn entry_len(&self) -> usize {
+        self.metadata.len
+    }
+
+    /// Writes this entry to `sink` in the format of [`Entry::format`].
+    ///
+    /// An entry read from a cpio file is written byte-identically, provided that the hexadecimal
+    /// digits of the original header are in uppercase as GNU cpio writes and that the padding
+    /// consists of zeros. Use [`write_trailer`] to terminate the cpio file after writing all
+    /// entries.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if `sink` fails, or if a field of an entry created by
+    /// [`EntryBuilder`] cannot be represented in the format.
+    pub fn write_to<W: Write + ?Sized>(&self, sink: &mut W) -> Result<(), WriteError<W::Error>> {
+        write::write_entry(self, self.format(), sink)
+    }
+
+    fn interpret_as_old_binary(binary: &'a [u8], offset: usize) -> Option<(Self, &'a [u8])> {
         const MAGIC: u16 = 0o070_707;
 
-        let mut byte_array = ByteArray::new(binary);
+        let mut byte_array = ByteArray::new(binary, offset);
 
         let magic = [byte_array.proceed_byte()?, byte_array.proceed_byte()?];
 
@@ -180,40 +421,52 @@ impl<'a> Entry<'a> {
             return None;
         }
 
+        let name_offset = byte_array.current;
+
         let name = byte_array.proceed_str((namesize - 1).into())?;
 
         byte_array.skip_bytes((namesize % 2 + 1).into()); // +1 for the terminating null character.
 
-        let file = byte_array.proceed_bytes(filesize.try_into().unwrap())?;
+        let data_offset = byte_array.current;
 
-        let mode = Mode::from_bits(mode.into())?;
+        let file = byte_array.proceed_bytes(filesize.try_into().ok()?)?;
+
+        let mode = Mode::from_bits_retain(mode.into());
+
+        byte_array.skip_bytes((filesize % 2).try_into().ok()?);
 
         let old_binary = Self {
-            dev: Some(dev.into()),
-            devmajor: None,
-            devminor: None,
-            ino: ino.into(),
-            mode,
-            uid: u_id.into(),
-            gid: g_id.into(),
-            nlink: nlink.into(),
-            rdev: Some(r_dev.into()),
-            rdevmajor: None,
-            rdevminor: None,
-            mtime: (mtime_most << 16) | mtime_least,
+            metadata: Metadata {
+                format: Format::OldBinary(endianness),
+                dev: Some(dev.into()),
+                devmajor: None,
+                devminor: None,
+                ino: ino.into(),
+                mode,
+                uid: u_id.into(),
+                gid: g_id.into(),
+                nlink: nlink.into(),
+                rdev: Some(r_dev.into()),
+                rdevmajor: None,
+                rdevminor: None,
+                mtime: (mtime_most << 16) | mtime_least,
+                check: None,
+                header_offset: offset,
+                name_offset,
+                data_offset,
+                len: byte_array.current - offset,
+            },
             name,
             file,
         };
 
-        byte_array.skip_bytes((filesize % 2).try_into().unwrap());
-
         Some((old_binary, byte_array.into_inner()))
     }
 
-    fn interpret_as_portable_ascii(binary: &'a [u8]) -> Option<(Self, &'a [u8])> {
+    fn interpret_as_portable_ascii(binary: &'a [u8], offset: usize) -> Option<(Self, &'a [u8])> {
         const MAGIC: &str = "070707";
 
-        let mut byte_array = ByteArray::new(binary);
+        let mut byte_array = ByteArray::new(binary, offset);
 
         let magic = byte_array.proceed_str(6)?;
 
@@ -236,27 +489,39 @@ impl<'a> Entry<'a> {
             return None;
         }
 
-        let name = byte_array.proceed_str((namesize - 1).try_into().unwrap())?;
+        let name_offset = byte_array.current;
+
+        let name = byte_array.proceed_str((namesize - 1).try_into().ok()?)?;
 
         byte_array.skip_bytes(1); // For the terminating '\0'.
 
-        let file = byte_array.proceed_bytes(filesize.try_into().unwrap())?;
+        let data_offset = byte_array.current;
 
-        let mode = Mode::from_bits(mode)?;
+        let file = byte_array.proceed_bytes(filesize.try_into().ok()?)?;
+
+        let mode = Mode::from_bits_retain(mode);
 
         let portable_ascii = Self {
-            dev: Some(dev),
-            devmajor: None,
-            devminor: None,
-            ino,
-            mode,
-            uid: u_id,
-            gid: g_id,
-            nlink,
-            rdev: Some(r_dev),
-            rdevmajor: None,
-            rdevminor: None,
-            mtime,
+            metadata: Metadata {
+                format: Format::PortableAscii,
+                dev: Some(dev),
+                devmajor: None,
+                devminor: None,
+                ino,
+                mode,
+                uid: u_id,
+                gid: g_id,
+                nlink,
+                rdev: Some(r_dev),
+                rdevmajor: None,
+                rdevminor: None,
+                mtime,
+                check: None,
+                header_offset: offset,
+                name_offset,
+                data_offset,
+                len: byte_array.current - offset,
+            },
             name,
             file,
         };
@@ -264,11 +529,11 @@ impl<'a> Entry<'a> {
         Some((portable_ascii, byte_array.into_inner()))
     }
 
-    fn interpret_as_new_ascii_or_crc(binary: &'a [u8]) -> Option<(Self, &'a [u8])> {
+    fn interpret_as_new_ascii_or_crc(binary: &'a [u8], offset: usize) -> Option<(Self, &'a [u8])> {
         const MAGIC_NEW_ASCII: &str = "070701";
         const MAGIC_CRC: &str = "070702";
 
-        let mut byte_array = ByteArray::new(binary);
+        let mut byte_array = ByteArray::new(binary, offset);
 
         let is_crc = match byte_array.proceed_str(6)? {
             MAGIC_CRC => true,
@@ -294,55 +559,113 @@ impl<'a> Entry<'a> {
             return None;
         }
 
-        let name = byte_array.proceed_str((namesize - 1).try_into().unwrap())?;
+        let name_offset = byte_array.current;
+
+        let name = byte_array.proceed_str((namesize - 1).try_into().ok()?)?;
 
         // For the terminating `\0`.
         byte_array.skip_bytes(1);
 
         byte_array.skip_to_next_multiple_of_four();
 
-        let file = byte_array.proceed_bytes(filesize.try_into().unwrap())?;
+        let data_offset = byte_array.current;
 
-        let mode = Mode::from_bits(mode)?;
+        let file = byte_array.proceed_bytes(filesize.try_into().ok()?)?;
 
-        let checksum = file
-            .iter()
-            .fold(0_u32, |acc, &x| acc.wrapping_add(x.into()));
+        let mode = Mode::from_bits_retain(mode);
 
         // Refer to line 1277, copyin.c, GNU cpio 2.13. It does not check the checksum of the
         // symbolic files.
-        if is_crc && !mode.contains(Mode::SYMBOLIK_LINK) && (checksum != check) {
+        if is_crc
+            && mode.bits() & FILE_TYPE_MASK != Mode::SYMBOLIK_LINK.bits()
+            && (checksum(file) != check)
+        {
             return None;
         }
 
+        byte_array.skip_to_next_multiple_of_four();
+
         let new_ascii = Self {
-            ino,
-            mode,
-            uid: u_id,
-            gid: g_id,
-            nlink,
-            mtime,
-            dev: None,
-            devmajor: Some(devmajor),
-            devminor: Some(devminor),
-            rdev: None,
-            rdevmajor: Some(r_devmajor),
-            rdevminor: Some(r_devminor),
+            metadata: Metadata {
+                format: if is_crc {
+                    Format::NewCrc
+                } else {
+                    Format::NewAscii
+                },
+                ino,
+                mode,
+                uid: u_id,
+                gid: g_id,
+                nlink,
+                mtime,
+                dev: None,
+                devmajor: Some(devmajor),
+                devminor: Some(devminor),
+                rdev: None,
+                rdevmajor: Some(r_devmajor),
+                rdevminor: Some(r_devminor),
+                check: is_crc.then_some(check),
+                header_offset: offset,
+                name_offset,
+                data_offset,
+                len: byte_array.current - offset,
+            },
             name,
             file,
         };
 
-        byte_array.skip_to_next_multiple_of_four();
-
         Some((new_ascii, byte_array.into_inner()))
     }
 
-    fn new(binary: &'a [u8]) -> Option<(Self, &'a [u8])> {
-        Self::interpret_as_old_binary(binary)
-            .or_else(|| Self::interpret_as_portable_ascii(binary))
-            .or_else(|| Self::interpret_as_new_ascii_or_crc(binary))
-            .filter(|(entry, _)| entry.name() != "TRAILER!!!")
+    fn new(binary: &'a [u8], offset: usize) -> Option<(Self, &'a [u8])> {
+        Self::new_including_trailer(binary, offset).filter(|(entry, _)| !entry.is_trailer())
+    }
+
+    fn new_including_trailer(binary: &'a [u8], offset: usize) -> Option<(Self, &'a [u8])> {
+        Self::interpret_as_old_binary(binary, offset)
+            .or_else(|| Self::interpret_as_portable_ascii(binary, offset))
+            .or_else(|| Self::interpret_as_new_ascii_or_crc(binary, offset))
     }
+
+    fn is_trailer(&self) -> bool {
+        self.name == TRAILER_NAME
+    }
+}
+
+/// The format of a cpio file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Format {
+    /// Old Binary Format with the given byte order.
+    OldBinary(Endianness),
+    /// Portable ASCII Format.
+    PortableAscii,
+    /// New ASCII Format.
+    NewAscii,
+    /// New CRC Format.
+    NewCrc,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Metadata {
+    format: Format,
+    dev: Option<u32>,
+    devmajor: Option<u32>,
+    devminor: Option<u32>,
+    ino: u32,
+    mode: Mode,
+    uid: u32,
+    gid: u32,
+    nlink: u32,
+    rdev: Option<u32>,
+    rdevmajor: Option<u32>,
+    rdevminor: Option<u32>,
+    mtime: u64,
+    check: Option<u32>,
+    header_offset: usize,
+    name_offset: usize,
+    data_offset: usize,
+    len: usize,
 }
 
 bitflags! {
@@ -395,22 +718,48 @@ bitflags! {
     }
 }
 
-struct Iter<'a>(&'a [u8]);
+fn checksum(file: &[u8]) -> u32 {
+    file.iter()
+        .fold(0_u32, |acc, &x| acc.wrapping_add(x.into()))
+}
+
+struct Iter<'a> {
+    binary: &'a [u8],
+    offset: usize,
+}
 impl<'a> Iter<'a> {
     fn new(binary: &'a [u8]) -> Self {
-        Self(binary)
+        Self { binary, offset: 0 }
+    }
+
+    /// Returns the trailer at which the iteration stopped, or [`None`] if the cpio file ends
+    /// without a trailer.
+    ///
+    /// This method returns the offset of the entry as an error if the iteration stopped because
+    /// the entry is corrupt.
+    fn trailer(&self) -> Result<Option<Entry<'a>>, usize> {
+        if self.binary.is_empty() {
+            Ok(None)
+        } else {
+            Entry::new_including_trailer(self.binary, self.offset)
+                .map(|(entry, _)| entry)
+                .filter(Entry::is_trailer)
+                .map(Some)
+                .ok_or(self.offset)
+        }
     }
 }
 impl<'a> Iterator for Iter<'a> {
     type Item = Entry<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.0.is_empty() {
+        if self.binary.is_empty() {
             None
         } else {
-            let (entry, remaining) = Entry::new(self.0)?;
+            let (entry, remaining) = Entry::new(self.binary, self.offset)?;
 
-            self.0 = remaining;
+            self.binary = remaining;
+            self.offset += entry.entry_len();
 
             Some(entry)
         }
@@ -422,8 +771,8 @@ struct ByteArray<'a> {
     current: usize,
 }
 impl<'a> ByteArray<'a> {
-    fn new(binary: &'a [u8]) -> Self {
-        Self { binary, current: 0 }
+    fn new(binary: &'a [u8], current: usize) -> Self {
+        Self { binary, current }
     }
 
     fn into_inner(self) -> &'a [u8] {
@@ -480,9 +829,13 @@ impl<'a> ByteArray<'a> {
     }
 }
 
+/// The byte order of Old Binary Format.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Endianness {
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Endianness {
+    /// Big endian.
     Big,
+    /// Little endian.
     Little,
 }
 impl Endianness {
@@ -492,4 +845,11 @@ impl Endianness {
             Self::Little => u16::from_le_bytes(bytes),
         }
     }
+
+    fn u16_to_u8_array(self, value: u16) -> [u8; 2] {
+        match self {
+            Self::Big => value.to_be_bytes(),
+            Self::Little => value.to_le_bytes(),
+        }
+    }
 }
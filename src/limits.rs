@@ -0,0 +1,191 @@
+use {
+    crate::{Entry, Iter},
+    core::fmt,
+};
+
+/// Limits on the resources used by a cpio file, which protect the readers of untrusted cpio files
+/// from pathological inputs.
+///
+/// No limits are imposed by default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ParseLimits {
+    entries: Option<usize>,
+    name_len: Option<usize>,
+    file_size: Option<u64>,
+    total_size: Option<u64>,
+    path_depth: Option<usize>,
+}
+impl ParseLimits {
+    /// Creates new limits which impose nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of entries, excluding the trailer.
+    #[must_use]
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.entries = Some(max_entries);
+        self
+    }
+
+    /// Sets the maximum length of a filename in bytes.
+    #[must_use]
+    pub fn max_name_len(mut self, max_name_len: usize) -> Self {
+        self.name_len = Some(max_name_len);
+        self
+    }
+
+    /// Sets the maximum size of the content of a single file.
+    #[must_use]
+    pub fn max_file_size(mut self, max_file_size: u64) -> Self {
+        self.file_size = Some(max_file_size);
+        self
+    }
+
+    /// Sets the maximum sum of the sizes of the contents of all files.
+    #[must_use]
+    pub fn max_total_size(mut self, max_total_size: u64) -> Self {
+        self.total_size = Some(max_total_size);
+        self
+    }
+
+    /// Sets the maximum number of components of a filename.
+    ///
+    /// Empty components and `.` are not counted, so the depth of both `a/b` and `./a//b` is 2.
+    #[must_use]
+    pub fn max_path_depth(mut self, max_path_depth: usize) -> Self {
+        self.path_depth = Some(max_path_depth);
+        self
+    }
+
+    /// Returns an iterator that iterates over each content of the given cpio file within the
+    /// limits.
+    ///
+    /// The iterator checks the headers in the same way as [`iter_files`](crate::iter_files). If
+    /// an entry exceeds a limit, the iterator returns the error and stops iterating.
+    pub fn iter_files<'a>(
+        &self,
+        cpio_binary: &'a [u8],
+    ) -> impl Iterator<Item = Result<Entry<'a>, LimitError>> {
+        LimitedIter {
+            iter: Iter::new(cpio_binary),
+            limits: *self,
+            entries: 0,
+            total_size: 0,
+            failed: false,
+        }
+    }
+
+    fn check(&self, entry: &Entry<'_>, entries: usize, total_size: u64) -> Result<(), LimitError> {
+        let offset = entry.header_offset();
+
+        if exceeds(entries, self.entries) {
+            Err(LimitError::TooManyEntries { offset })
+        } else if exceeds(entry.name().len(), self.name_len) {
+            Err(LimitError::NameTooLong { offset })
+        } else if exceeds(entry.size(), self.file_size) {
+            Err(LimitError::FileTooLarge { offset })
+        } else if exceeds(total_size, self.total_size) {
+            Err(LimitError::TotalSizeTooLarge { offset })
+        } else if exceeds(entry.path().depth(), self.path_depth) {
+            Err(LimitError::PathTooDeep { offset })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// An error which occurs when a cpio file exceeds one of the [`ParseLimits`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum LimitError {
+    /// The cpio file contains too many entries.
+    TooManyEntries {
+        /// The offset of the first entry exceeding the limit from the start of the cpio file.
+        offset: usize,
+    },
+    /// The filename is too long.
+    NameTooLong {
+        /// The offset of the entry from the start of the cpio file.
+        offset: usize,
+    },
+    /// The content of the file is too large.
+    FileTooLarge {
+        /// The offset of the entry from the start of the cpio file.
+        offset: usize,
+    },
+    /// The sum of the sizes of the contents is too large.
+    TotalSizeTooLarge {
+        /// The offset of the first entry exceeding the limit from the start of the cpio file.
+        offset: usize,
+    },
+    /// The filename has too many components.
+    PathTooDeep {
+        /// The offset of the entry from the start of the cpio file.
+        offset: usize,
+    },
+}
+impl LimitError {
+    /// Returns the offset of the header of the entry which exceeds the limit.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooManyEntries { offset }
+            | Self::NameTooLong { offset }
+            | Self::FileTooLarge { offset }
+            | Self::TotalSizeTooLarge { offset }
+            | Self::PathTooDeep { offset } => *offset,
+        }
+    }
+}
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limit = match self {
+            Self::TooManyEntries { .. } => "the number of entries",
+            Self::NameTooLong { .. } => "the length of the filename",
+            Self::FileTooLarge { .. } => "the size of the file",
+            Self::TotalSizeTooLarge { .. } => "the total size of the files",
+            Self::PathTooDeep { .. } => "the depth of the path",
+        };
+
+        write!(f, "{limit} exceeds the limit at offset {}", self.offset())
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for LimitError {}
+
+struct LimitedIter<'a> {
+    iter: Iter<'a>,
+    limits: ParseLimits,
+    entries: usize,
+    total_size: u64,
+    failed: bool,
+}
+impl<'a> Iterator for LimitedIter<'a> {
+    type Item = Result<Entry<'a>, LimitError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let entry = self.iter.next()?;
+
+        self.entries += 1;
+        self.total_size = self.total_size.saturating_add(entry.size());
+
+        let result = self
+            .limits
+            .check(&entry, self.entries, self.total_size)
+            .map(|()| entry);
+
+        self.failed = result.is_err();
+
+        Some(result)
+    }
+}
+
+fn exceeds<T: PartialOrd + Copy>(value: T, max: Option<T>) -> bool {
+    max.is_some_and(|max| value > max)
+}
@@ -0,0 +1,110 @@
+use crate::{Entry, Iter};
+#[cfg(feature = "alloc")]
+use {crate::DeviceNumber, alloc::collections::BTreeMap};
+
+/// An index of the contents of the files with multiple links in a cpio file.
+///
+/// New ASCII Format and New CRC Format store the content of such a file in only one of its links,
+/// leaving the others empty. With the `alloc` feature, this index is built in one pass over the
+/// cpio file. Otherwise, the cpio file is scanned again for each lookup, which takes quadratic time
+/// for a cpio file containing many links.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HardLinks<'a> {
+    #[cfg(feature = "alloc")]
+    links: BTreeMap<(DeviceNumber, u32), Link<'a>>,
+    #[cfg(not(feature = "alloc"))]
+    cpio_binary: &'a [u8],
+}
+impl<'a> HardLinks<'a> {
+    /// Indexes the contents of the files with multiple links in the given cpio file.
+    ///
+    /// The entries after a corrupt one are ignored.
+    #[must_use]
+    pub fn new(cpio_binary: &'a [u8]) -> Self {
+        Self {
+            #[cfg(feature = "alloc")]
+            links: index(cpio_binary),
+            #[cfg(not(feature = "alloc"))]
+            cpio_binary,
+        }
+    }
+
+    /// Returns the content of `entry`, which is stored in another link to the same file if
+    /// `entry` is a hard link without content.
+    ///
+    /// `entry` must be read from the indexed cpio file.
+    #[must_use]
+    pub fn content(&self, entry: &Entry<'a>) -> &'a [u8] {
+        if entry.nlink() <= 1 || !entry.file().is_empty() {
+            return entry.file();
+        }
+
+        #[cfg(feature = "alloc")]
+        let content = self
+            .links
+            .get(&(entry.device(), entry.ino()))
+            .map(|link| link.content);
+        #[cfg(not(feature = "alloc"))]
+        let content = Iter::new(self.cpio_binary)
+            .filter(|link| is_same_file(link, entry) && !link.file().is_empty())
+            .last()
+            .map(|link| link.file());
+
+        content.unwrap_or(entry.file())
+    }
+
+    /// Returns `true` if no other link to the file of `entry` follows it, which is the link New
+    /// ASCII Format and New CRC Format store the content in.
+    ///
+    /// `entry` must be read from the indexed cpio file.
+    pub(crate) fn is_last(&self, entry: &Entry<'a>) -> bool {
+        if entry.nlink() <= 1 {
+            return true;
+        }
+
+        #[cfg(feature = "alloc")]
+        let last_offset = self
+            .links
+            .get(&(entry.device(), entry.ino()))
+            .map(|link| link.last_offset);
+        #[cfg(not(feature = "alloc"))]
+        let last_offset = Iter::new(self.cpio_binary)
+            .filter(|link| is_same_file(link, entry))
+            .last()
+            .map(|link| link.header_offset());
+
+        last_offset.unwrap_or(entry.header_offset()) == entry.header_offset()
+    }
+}
+
+/// The links to a file in a cpio file.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+struct Link<'a> {
+    /// The content stored in one of the links, which is empty if none of them has it.
+    content: &'a [u8],
+    /// The offset of the header of the last link from the start of the cpio file.
+    last_offset: usize,
+}
+
+#[cfg(feature = "alloc")]
+fn index(cpio_binary: &[u8]) -> BTreeMap<(DeviceNumber, u32), Link<'_>> {
+    let mut links = BTreeMap::new();
+
+    for entry in Iter::new(cpio_binary).filter(|entry| entry.nlink() > 1) {
+        let link: &mut Link<'_> = links.entry((entry.device(), entry.ino())).or_default();
+
+        if !entry.file().is_empty() {
+            link.content = entry.file();
+        }
+
+        link.last_offset = entry.header_offset();
+    }
+
+    links
+}
+
+#[cfg(not(feature = "alloc"))]
+fn is_same_file(a: &Entry<'_>, b: &Entry<'_>) -> bool {
+    a.ino() == b.ino() && a.device() == b.device()
+}
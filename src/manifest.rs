@@ -0,0 +1,115 @@
+use {
+    crate::{Entry, HardLinks, Iter, Mode, FILE_TYPE_MASK},
+    core::fmt::{self, Write},
+    digest::Digest,
+};
+
+/// A generator of a manifest which lists the digests of the regular files in a cpio file.
+///
+/// Each line of the manifest is `hash  path`, where `hash` is the lowercase hexadecimal digest of
+/// the content and `path` is the normalized [`EntryPath`](crate::EntryPath) relative to the root
+/// of the cpio file. `sha256sum -c` and the similar tools verify it in the directory to which the
+/// cpio file is extracted. If the metadata is enabled with [`Manifest::metadata`], each line is
+/// `hash  mode  uid:gid  path` with the octal mode, which these tools cannot read.
+///
+/// As these tools do, a path containing a backslash or a newline is escaped with backslashes, and
+/// the line starts with a backslash.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Manifest {
+    metadata: bool,
+}
+impl Manifest {
+    /// Creates a new generator which writes the lines in the format of `sha256sum`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { metadata: false }
+    }
+
+    /// Sets whether the lines include the mode and the owner of the files.
+    ///
+    /// The metadata is disabled by default. Enabling it makes the manifest unreadable for
+    /// `sha256sum -c`.
+    #[must_use]
+    pub fn metadata(mut self, metadata: bool) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Writes the manifest of the given cpio file to `sink`, hashing the contents with `D`.
+    ///
+    /// The hard links without contents in New ASCII Format and New CRC Format are hashed with the
+    /// content stored in another link to the same file, which is looked up with [`HardLinks`].
+    /// The entries after a corrupt one are ignored.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if `sink` fails.
+    pub fn write<D: Digest, W: Write + ?Sized>(
+        &self,
+        cpio_binary: &[u8],
+        sink: &mut W,
+    ) -> fmt::Result {
+        let links = HardLinks::new(cpio_binary);
+
+        for entry in Iter::new(cpio_binary) {
+            if entry.mode().bits() & FILE_TYPE_MASK == Mode::REGULAR_FILE.bits() {
+                self.write_line::<D, _>(&entry, links.content(&entry), sink)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_line<D: Digest, W: Write + ?Sized>(
+        self,
+        entry: &Entry<'_>,
+        content: &[u8],
+        sink: &mut W,
+    ) -> fmt::Result {
+        let path = entry.path();
+        let escaped = path
+            .components()
+            .any(|component| component.as_str().contains(['\\', '\n']));
+
+        if escaped {
+            sink.write_char('\\')?;
+        }
+
+        for byte in D::digest(content) {
+            write!(sink, "{byte:02x}")?;
+        }
+
+        if self.metadata {
+            write!(
+                sink,
+                "  {:o}  {}:{}",
+                entry.mode().bits(),
+                entry.uid(),
+                entry.gid()
+            )?;
+        }
+
+        sink.write_str("  ")?;
+
+        for (i, component) in path.components().enumerate() {
+            if i > 0 {
+                sink.write_char('/')?;
+            }
+
+            for c in component.as_str().chars() {
+                match c {
+                    '\\' if escaped => sink.write_str("\\\\")?,
+                    '\n' if escaped => sink.write_str("\\n")?,
+                    c => sink.write_char(c)?,
+                }
+            }
+        }
+
+        sink.write_char('\n')
+    }
+}
+impl Default for Manifest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,157 @@
+use {
+    crate::{Mode, FILE_TYPE_MASK},
+    core::{fmt, str::FromStr},
+};
+
+/// The largest mode which is parsed from an octal number, which fits in the 16-bit `mode_t` of
+/// Old Binary Format.
+const MAX_OCTAL: u32 = 0o177_777;
+/// The characters of the file types in the symbolic form, and the corresponding bits.
+const FILE_TYPES: [(char, Mode); 7] = [
+    ('p', Mode::NAMED_PIPE_FIFO),
+    ('c', Mode::CHARACTER_SPECIAL_DEVICE),
+    ('d', Mode::DIRECTORY),
+    ('b', Mode::BLOCK_SPECIAL_DEVICE),
+    ('-', Mode::REGULAR_FILE),
+    ('l', Mode::SYMBOLIK_LINK),
+    ('s', Mode::SOCKET),
+];
+/// The shifts of the permission bits of the user, the group and the others, with the special bit
+/// shown in the execute position and its character.
+const CLASSES: [(u32, Mode, char); 3] = [
+    (6, Mode::SUID, 's'),
+    (3, Mode::SGID, 's'),
+    (0, Mode::STICKY, 't'),
+];
+
+impl Mode {
+    /// Returns the bits which represent the file type, including the types not declared in
+    /// [`Mode`] such as whiteouts.
+    ///
+    /// Compare the result with a file type such as [`Mode::DIRECTORY`]. [`Mode::contains`] does
+    /// not work for this purpose because the file types share bits.
+    #[must_use]
+    pub const fn file_type(self) -> Self {
+        Self::from_bits_retain(self.bits() & FILE_TYPE_MASK)
+    }
+}
+impl fmt::Display for Mode {
+    /// Displays the mode in the symbolic form of `ls -l`, such as `drwxr-xr-x`.
+    ///
+    /// The file type is shown as `?` if it is unknown.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file_type = FILE_TYPES
+            .iter()
+            .find(|(_, file_type)| self.file_type() == *file_type)
+            .map_or('?', |(c, _)| *c);
+
+        write!(f, "{file_type}")?;
+
+        for (shift, special, set) in CLASSES {
+            let bits = (self.bits() >> shift) & 0o7;
+
+            let execute = match (bits & 0o1 != 0, self.contains(special)) {
+                (true, true) => set,
+                (false, true) => set.to_ascii_uppercase(),
+                (true, false) => 'x',
+                (false, false) => '-',
+            };
+
+            write!(
+                f,
+                "{}{}{}",
+                if bits & 0o4 == 0 { '-' } else { 'r' },
+                if bits & 0o2 == 0 { '-' } else { 'w' },
+                execute
+            )?;
+        }
+
+        Ok(())
+    }
+}
+impl FromStr for Mode {
+    type Err = ParseModeError;
+
+    /// Parses either the symbolic form of `ls -l` such as `drwxr-xr-x` or an octal number such as
+    /// `0755`, `100644` and `0o755`.
+    ///
+    /// An octal number may contain the file types not declared in [`Mode`], such as `0160000`,
+    /// but it must not exceed `0177777`.
+    ///
+    /// The file type may be omitted from the symbolic form, such as `rwxr-xr-x`, or be `?`, which
+    /// [`Display`](fmt::Display) shows for a mode without a file type. Both mean no file type bits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.bytes().next().is_some_and(|b| b.is_ascii_digit()) {
+            parse_octal(s)
+        } else {
+            parse_symbolic(s)
+        }
+        .ok_or(ParseModeError)
+    }
+}
+
+/// An error which occurs while parsing a [`Mode`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParseModeError;
+impl fmt::Display for ParseModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid mode")
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for ParseModeError {}
+
+fn parse_octal(s: &str) -> Option<Mode> {
+    let digits = s.strip_prefix("0o").unwrap_or(s);
+
+    if digits.is_empty() || !digits.bytes().all(|b| matches!(b, b'0'..=b'7')) {
+        return None;
+    }
+
+    let bits = u32::from_str_radix(digits, 8).ok()?;
+
+    (bits <= MAX_OCTAL).then_some(Mode::from_bits_retain(bits))
+}
+
+fn parse_symbolic(s: &str) -> Option<Mode> {
+    let chars = s.as_bytes();
+
+    let (mut mode, permissions) = match chars.len() {
+        9 => (Mode::empty(), chars),
+        10 if chars[0] == b'?' => (Mode::empty(), &chars[1..]),
+        10 => {
+            let file_type = FILE_TYPES
+                .iter()
+                .find(|(c, _)| *c == char::from(chars[0]))?;
+
+            (file_type.1, &chars[1..])
+        }
+        _ => return None,
+    };
+
+    for ((shift, special, set), class) in CLASSES.iter().zip(permissions.chunks(3)) {
+        let read = match class[0] {
+            b'r' => 0o4,
+            b'-' => 0,
+            _ => return None,
+        };
+        let write = match class[1] {
+            b'w' => 0o2,
+            b'-' => 0,
+            _ => return None,
+        };
+        let (execute, special_set) = match char::from(class[2]) {
+            'x' => (0o1, false),
+            '-' => (0, false),
+            c if c == *set => (0o1, true),
+            c if c == set.to_ascii_uppercase() => (0, true),
+            _ => return None,
+        };
+
+        mode |= Mode::from_bits((read | write | execute) << shift)?;
+        mode.set(*special, special_set);
+    }
+
+    Some(mode)
+}
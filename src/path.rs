@@ -0,0 +1,227 @@
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// The filename of an entry, which is compared component-wise.
+///
+/// cpio files name the same file in various ways, such as `./usr/bin/sh`, `usr/bin/sh`,
+/// `/usr/bin/sh` and `usr//bin/sh`. This type ignores the leading `/`, the empty components and
+/// the `.` components, so all of them are equal and are displayed as `usr/bin/sh`. The `..`
+/// components are kept as they are because resolving them requires following symbolic links.
+#[derive(Copy, Clone, Debug)]
+pub struct EntryPath<'a>(&'a str);
+impl<'a> EntryPath<'a> {
+    /// Creates a new path from the given filename.
+    #[must_use]
+    pub fn new(name: &'a str) -> Self {
+        Self(name)
+    }
+
+    /// Returns the filename as it is stored in the cpio file.
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Returns `true` if the filename starts with `/`.
+    #[must_use]
+    pub fn is_absolute(&self) -> bool {
+        self.0.starts_with('/')
+    }
+
+    /// Returns the filename without the leading `./` and `/`.
+    ///
+    /// For example, both `./usr/bin/sh` and `//usr/bin/sh` become `usr/bin/sh`, and `.` becomes an
+    /// empty string.
+    #[must_use]
+    pub fn trim_leading(&self) -> &'a str {
+        let mut name = self.0;
+
+        loop {
+            if let Some(stripped) = name.strip_prefix('/') {
+                name = stripped;
+            } else if let Some(stripped) = name.strip_prefix("./") {
+                name = stripped;
+            } else if name == "." {
+                return "";
+            } else {
+                return name;
+            }
+        }
+    }
+
+    /// Returns an iterator over the components of the path, skipping the empty components and
+    /// the `.` components.
+    #[must_use]
+    pub fn components(&self) -> impl DoubleEndedIterator<Item = Component<'a>> + Clone {
+        self.0
+            .split('/')
+            .filter(|component| !component.is_empty() && *component != ".")
+            .map(|component| match component {
+                ".." => Component::ParentDir,
+                normal => Component::Normal(normal),
+            })
+    }
+
+    /// Returns the number of the components of the path.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.components().count()
+    }
+
+    /// Returns the last component of the path, or [`None`] if it does not exist or is `..`.
+    #[must_use]
+    pub fn file_name(&self) -> Option<&'a str> {
+        match self.components().next_back()? {
+            Component::Normal(name) => Some(name),
+            Component::ParentDir => None,
+        }
+    }
+
+    /// Returns the path without its last component, or [`None`] if the path has no components.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        let mut name = self.0;
+
+        loop {
+            name = name.trim_end_matches('/');
+
+            if name == "." || name.ends_with("/.") {
+                name = &name[..name.len() - 1];
+            } else if name.is_empty() {
+                return None;
+            } else {
+                break;
+            }
+        }
+
+        Some(Self(name.rfind('/').map_or("", |i| {
+            if i == 0 {
+                "/"
+            } else {
+                &name[..i]
+            }
+        })))
+    }
+
+    /// Returns `true` if the components of `base` are a prefix of those of this path.
+    #[must_use]
+    pub fn starts_with(&self, base: EntryPath<'_>) -> bool {
+        let mut components = self.components();
+
+        base.components()
+            .all(|component| components.next() == Some(component))
+    }
+
+    /// Returns `true` if the `..` components lead the path outside of the directory containing
+    /// it, such as `../etc/passwd` and `usr/../../etc/passwd`.
+    ///
+    /// The path is checked lexically without following symbolic links.
+    #[must_use]
+    pub fn escapes(&self) -> bool {
+        let mut depth = 0_usize;
+
+        self.components().any(|component| match component {
+            Component::Normal(_) => {
+                depth += 1;
+
+                false
+            }
+            Component::ParentDir => match depth.checked_sub(1) {
+                Some(parent) => {
+                    depth = parent;
+
+                    false
+                }
+                None => true,
+            },
+        })
+    }
+
+    /// Joins the path to `root`, resolving the `..` components lexically.
+    ///
+    /// The leading `/` is ignored, so the returned path is always under `root`. This method returns
+    /// [`None`] if the path [escapes](EntryPath::escapes) `root`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn join_to(&self, root: &std::path::Path) -> Option<std::path::PathBuf> {
+        if self.escapes() {
+            return None;
+        }
+
+        let mut path = root.to_path_buf();
+
+        for component in self.components() {
+            match component {
+                Component::Normal(name) => path.push(name),
+                Component::ParentDir => {
+                    path.pop();
+                }
+            }
+        }
+
+        Some(path)
+    }
+}
+impl PartialEq for EntryPath<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.components().eq(other.components())
+    }
+}
+impl Eq for EntryPath<'_> {}
+impl PartialOrd for EntryPath<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for EntryPath<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.components().cmp(other.components())
+    }
+}
+impl Hash for EntryPath<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for component in self.components() {
+            component.hash(state);
+        }
+    }
+}
+impl fmt::Display for EntryPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, component) in self.components().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+
+            f.write_str(component.as_str())?;
+        }
+
+        Ok(())
+    }
+}
+impl<'a> From<&'a str> for EntryPath<'a> {
+    fn from(name: &'a str) -> Self {
+        Self::new(name)
+    }
+}
+
+/// A component of an [`EntryPath`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Component<'a> {
+    /// `..`.
+    ParentDir,
+    /// A filename.
+    Normal(&'a str),
+}
+impl<'a> Component<'a> {
+    /// Returns the component as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            Self::ParentDir => "..",
+            Self::Normal(name) => name,
+        }
+    }
+}
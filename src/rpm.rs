@@ -0,0 +1,476 @@
+use {
+    crate::{DeviceEncoding, Entry, EntryBuilder, Format, Iter, Mode},
+    core::{convert::TryFrom, fmt, str},
+    std::{
+        borrow::ToOwned,
+        collections::HashMap,
+        format,
+        io::{self, Read},
+        string::{String, ToString},
+        vec::Vec,
+    },
+};
+
+/// The magic value at the start of the lead of an RPM package.
+const LEAD_MAGIC: [u8; 4] = [0xed, 0xab, 0xee, 0xdb];
+/// The length of the lead of an RPM package.
+const LEAD_LEN: usize = 96;
+/// The magic value at the start of a header structure.
+const HEADER_MAGIC: [u8; 4] = [0x8e, 0xad, 0xe8, 0x01];
+/// The length of the fixed part of a header structure, and of each of its index entries.
+const HEADER_ENTRY_LEN: usize = 16;
+
+/// The magic value of an entry of the stripped cpio format.
+const STRIPPED_MAGIC: &[u8] = b"07070X";
+/// The length of the header of an entry of the stripped cpio format.
+const STRIPPED_HEADER_LEN: usize = 6 + 8;
+
+const TAG_FILESIZES: u32 = 1028;
+const TAG_FILEMODES: u32 = 1030;
+const TAG_FILERDEVS: u32 = 1033;
+const TAG_FILEMTIMES: u32 = 1034;
+const TAG_FILEDEVICES: u32 = 1095;
+const TAG_FILEINODES: u32 = 1096;
+const TAG_DIRINDEXES: u32 = 1116;
+const TAG_BASENAMES: u32 = 1117;
+const TAG_DIRNAMES: u32 = 1118;
+const TAG_PAYLOADFORMAT: u32 = 1124;
+const TAG_PAYLOADCOMPRESSOR: u32 = 1125;
+const TAG_LONGFILESIZES: u32 = 5008;
+
+const TYPE_INT16: u32 = 3;
+const TYPE_INT32: u32 = 4;
+const TYPE_INT64: u32 = 5;
+const TYPE_STRING: u32 = 6;
+const TYPE_STRING_ARRAY: u32 = 8;
+
+/// The decompressed cpio payload of an RPM package.
+///
+/// Since RPM 4.12, packages containing files larger than 4 GiB use the stripped cpio format whose
+/// entries have the magic value `07070X` and only the index of the file in the package header.
+/// The metadata of such entries is read from the header, and [`RpmPayload::iter_files`] yields
+/// them as entries of New ASCII Format. Note that RPM records the owners of files by name, so
+/// [`Entry::uid`] and [`Entry::gid`] of these entries are 0.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RpmPayload {
+    payload: Vec<u8>,
+    compressor: String,
+    files: Vec<FileInfo>,
+}
+impl RpmPayload {
+    /// Locates the payload of the given RPM package and decompresses it.
+    ///
+    /// The payload compressed with gzip, xz, lzma or zstd is supported. An uncompressed payload
+    /// is also accepted.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if `rpm` is not a valid RPM package, if the payload is not a
+    /// cpio file or is compressed with an unsupported algorithm, or if decompression fails.
+    pub fn new(rpm: &[u8]) -> Result<Self, RpmError> {
+        if rpm.get(..4) != Some(&LEAD_MAGIC[..]) || rpm.len() < LEAD_LEN {
+            return Err(RpmError::InvalidLead);
+        }
+
+        let (_, signature_end) = Header::parse(rpm, LEAD_LEN).ok_or(RpmError::InvalidHeader)?;
+
+        // The signature is padded to a multiple of 8 bytes.
+        let header_start = signature_end + (8 - signature_end % 8) % 8;
+
+        let (header, payload_start) =
+            Header::parse(rpm, header_start).ok_or(RpmError::InvalidHeader)?;
+
+        match header.string(TAG_PAYLOADFORMAT) {
+            None | Some("cpio") => {}
+            Some(format) => return Err(RpmError::UnsupportedPayloadFormat(format.to_owned())),
+        }
+
+        // RPM assumes gzip if the compressor is not recorded.
+        let compressor = header.string(TAG_PAYLOADCOMPRESSOR).unwrap_or("gzip");
+
+        let payload = decompress(compressor, &rpm[payload_start..])?;
+
+        let files = if payload.starts_with(STRIPPED_MAGIC) {
+            FileInfo::read_all(&header).ok_or(RpmError::InvalidHeader)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            payload,
+            compressor: compressor.to_owned(),
+            files,
+        })
+    }
+
+    /// Returns the name of the algorithm with which the payload was compressed, as recorded in
+    /// the package header (e.g., `"gzip"`).
+    #[must_use]
+    pub fn compressor(&self) -> &str {
+        &self.compressor
+    }
+
+    /// Returns the decompressed payload.
+    ///
+    /// Unless [`RpmPayload::is_stripped`] returns `true`, the payload is a cpio file of New
+    /// ASCII Format which can be read by [`iter_files`](crate::iter_files).
+    #[must_use]
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Returns `true` if the payload is in the stripped cpio format used for files larger than
+    /// 4 GiB.
+    #[must_use]
+    pub fn is_stripped(&self) -> bool {
+        self.payload.starts_with(STRIPPED_MAGIC)
+    }
+
+    /// Returns an iterator that iterates over each content of the payload.
+    ///
+    /// As with [`iter_files`](crate::iter_files), the iterator stops at a corrupt entry.
+    pub fn iter_files(&self) -> impl Iterator<Item = Entry<'_>> {
+        let (iter, stripped) = if self.is_stripped() {
+            (None, Some(StrippedIter::new(self)))
+        } else {
+            (Some(Iter::new(&self.payload)), None)
+        };
+
+        iter.into_iter()
+            .flatten()
+            .chain(stripped.into_iter().flatten())
+    }
+}
+
+/// An error which occurs while reading an RPM package.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum RpmError {
+    /// The lead of the package is corrupt.
+    InvalidLead,
+    /// The signature or the header of the package is corrupt.
+    InvalidHeader,
+    /// The payload is not a cpio file.
+    UnsupportedPayloadFormat(String),
+    /// The payload is compressed with an unsupported algorithm.
+    UnsupportedCompressor(String),
+    /// Decompressing the payload failed.
+    Decompress(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_io_error"))] io::Error,
+    ),
+}
+impl fmt::Display for RpmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLead => write!(f, "the lead of the RPM package is corrupt"),
+            Self::InvalidHeader => write!(f, "the header of the RPM package is corrupt"),
+            Self::UnsupportedPayloadFormat(format) => {
+                write!(f, "unsupported payload format `{format}`")
+            }
+            Self::UnsupportedCompressor(compressor) => {
+                write!(f, "unsupported payload compressor `{compressor}`")
+            }
+            Self::Decompress(e) => write!(f, "failed to decompress the payload: {e}"),
+        }
+    }
+}
+impl std::error::Error for RpmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decompress(e) => Some(e),
+            Self::InvalidLead
+            | Self::InvalidHeader
+            | Self::UnsupportedPayloadFormat(_)
+            | Self::UnsupportedCompressor(_) => None,
+        }
+    }
+}
+
+fn decompress(compressor: &str, compressed: &[u8]) -> Result<Vec<u8>, RpmError> {
+    let mut payload = Vec::new();
+
+    let result = match compressor {
+        "gzip" => flate2::read::GzDecoder::new(compressed)
+            .read_to_end(&mut payload)
+            .map(drop),
+        "xz" => lzma_rs::xz_decompress(&mut &*compressed, &mut payload).map_err(lzma_error),
+        "lzma" => lzma_rs::lzma_decompress(&mut &*compressed, &mut payload).map_err(lzma_error),
+        "zstd" => ruzstd::decoding::StreamingDecoder::new(compressed)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+            .and_then(|mut decoder| decoder.read_to_end(&mut payload))
+            .map(drop),
+        "identity" => {
+            payload.extend_from_slice(compressed);
+
+            Ok(())
+        }
+        _ => return Err(RpmError::UnsupportedCompressor(compressor.to_owned())),
+    };
+
+    result.map_err(RpmError::Decompress)?;
+
+    Ok(payload)
+}
+
+fn lzma_error(e: lzma_rs::error::Error) -> io::Error {
+    match e {
+        lzma_rs::error::Error::IoError(e) | lzma_rs::error::Error::HeaderTooShort(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, e.to_string()),
+    }
+}
+
+/// The metadata of a file recorded in the package header.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct FileInfo {
+    name: String,
+    mode: u32,
+    ino: u32,
+    nlink: u32,
+    rdev: u32,
+    mtime: u64,
+    size: u64,
+    /// The position of this file in its hard link set, counting from 1.
+    link_index: u32,
+}
+impl FileInfo {
+    fn read_all(header: &Header<'_>) -> Option<Vec<Self>> {
+        let basenames = header.string_array(TAG_BASENAMES)?;
+        let dirnames = header.string_array(TAG_DIRNAMES)?;
+        let dirindexes = header.integers(TAG_DIRINDEXES)?;
+        let modes = header.integers(TAG_FILEMODES)?;
+        let sizes = header
+            .integers(TAG_LONGFILESIZES)
+            .or_else(|| header.integers(TAG_FILESIZES))?;
+        let rdevs = header.integers(TAG_FILERDEVS)?;
+        let mtimes = header.integers(TAG_FILEMTIMES)?;
+        let devices = header.integers(TAG_FILEDEVICES)?;
+        let inodes = header.integers(TAG_FILEINODES)?;
+
+        let count = basenames.len();
+
+        let lengths = [
+            dirindexes.len(),
+            modes.len(),
+            sizes.len(),
+            rdevs.len(),
+            mtimes.len(),
+            devices.len(),
+            inodes.len(),
+        ];
+
+        if lengths.iter().any(|len| *len != count) {
+            return None;
+        }
+
+        let mut links = HashMap::new();
+
+        for i in 0..count {
+            *links.entry((devices[i], inodes[i])).or_insert(0) += 1;
+        }
+
+        let mut seen = HashMap::new();
+
+        (0..count)
+            .map(|i| {
+                let dirname = dirnames.get(usize::try_from(dirindexes[i]).ok()?)?;
+
+                let link_index = seen.entry((devices[i], inodes[i])).or_insert(0);
+                *link_index += 1;
+
+                Some(Self {
+                    // RPM stores the paths in the payload relative to the root.
+                    name: format!(".{}{}", dirname, basenames[i]),
+                    mode: u32::try_from(modes[i]).ok()?,
+                    ino: u32::try_from(inodes[i]).ok()?,
+                    nlink: links[&(devices[i], inodes[i])],
+                    rdev: u32::try_from(rdevs[i]).ok()?,
+                    mtime: mtimes[i],
+                    size: sizes[i],
+                    link_index: *link_index,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the length of the content stored in the payload.
+    ///
+    /// As in the other cpio formats, only the last one of hard links has the content.
+    fn stored_size(&self) -> u64 {
+        let file_type = Mode::from_bits_retain(self.mode).file_type();
+
+        if file_type == Mode::SYMBOLIK_LINK
+            || (file_type == Mode::REGULAR_FILE && self.link_index == self.nlink)
+        {
+            self.size
+        } else {
+            0
+        }
+    }
+}
+
+struct StrippedIter<'a> {
+    payload: &'a RpmPayload,
+    offset: usize,
+}
+impl<'a> StrippedIter<'a> {
+    fn new(payload: &'a RpmPayload) -> Self {
+        Self { payload, offset: 0 }
+    }
+}
+impl<'a> Iterator for StrippedIter<'a> {
+    type Item = Entry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let payload = &self.payload.payload;
+
+        // The stripped entries are followed by the ordinary trailer of New ASCII Format.
+        let header = payload.get(self.offset..)?.get(..STRIPPED_HEADER_LEN)?;
+
+        if !header.starts_with(STRIPPED_MAGIC) {
+            return None;
+        }
+
+        let index = str::from_utf8(&header[STRIPPED_MAGIC.len()..])
+            .ok()
+            .and_then(|index| usize::from_str_radix(index, 16).ok())?;
+
+        let info = self.payload.files.get(index)?;
+
+        let data_offset = next_multiple_of_four(self.offset + STRIPPED_HEADER_LEN);
+        let data_end = data_offset.checked_add(usize::try_from(info.stored_size()).ok()?)?;
+
+        let file = payload.get(data_offset..data_end)?;
+
+        let (rdevmajor, rdevminor) = DeviceEncoding::Linux.decode(info.rdev);
+
+        let mut entry = EntryBuilder::new(&info.name, Format::NewAscii)
+            .ino(info.ino)
+            .mode(Mode::from_bits_retain(info.mode))
+            .nlink(info.nlink)
+            .rdevmajor(rdevmajor)
+            .rdevminor(rdevminor)
+            .mtime(info.mtime)
+            .file(file)
+            .build();
+
+        let len = next_multiple_of_four(data_end) - self.offset;
+
+        entry.metadata.header_offset = self.offset;
+        entry.metadata.data_offset = data_offset;
+        entry.metadata.len = len;
+
+        self.offset += len;
+
+        Some(entry)
+    }
+}
+
+/// A header structure of an RPM package, which is used for both the signature and the header.
+struct Header<'a> {
+    index: &'a [u8],
+    store: &'a [u8],
+}
+impl<'a> Header<'a> {
+    /// Parses the header structure at `offset`, and returns it with the offset of its end.
+    fn parse(rpm: &'a [u8], offset: usize) -> Option<(Self, usize)> {
+        let intro = rpm.get(offset..)?.get(..HEADER_ENTRY_LEN)?;
+
+        if intro[..4] != HEADER_MAGIC {
+            return None;
+        }
+
+        let entries = usize::try_from(be_u32(&intro[8..])).ok()?;
+        let store_len = usize::try_from(be_u32(&intro[12..])).ok()?;
+
+        let index_start = offset + HEADER_ENTRY_LEN;
+        let store_start = index_start.checked_add(entries.checked_mul(HEADER_ENTRY_LEN)?)?;
+        let end = store_start.checked_add(store_len)?;
+
+        Some((
+            Self {
+                index: rpm.get(index_start..store_start)?,
+                store: rpm.get(store_start..end)?,
+            },
+            end,
+        ))
+    }
+
+    /// Returns the type, the data and the count of the given tag.
+    fn find(&self, tag: u32) -> Option<(u32, &'a [u8], usize)> {
+        let entry = self
+            .index
+            .chunks_exact(HEADER_ENTRY_LEN)
+            .find(|entry| be_u32(entry) == tag)?;
+
+        let offset = usize::try_from(be_u32(&entry[8..])).ok()?;
+        let count = usize::try_from(be_u32(&entry[12..])).ok()?;
+
+        Some((be_u32(&entry[4..]), self.store.get(offset..)?, count))
+    }
+
+    fn string(&self, tag: u32) -> Option<&'a str> {
+        match self.find(tag)? {
+            (TYPE_STRING, data, _) => data.split(|b| *b == 0).next().and_then(utf8),
+            _ => None,
+        }
+    }
+
+    fn string_array(&self, tag: u32) -> Option<Vec<&'a str>> {
+        match self.find(tag)? {
+            (TYPE_STRING_ARRAY, data, count) => {
+                let strings = data
+                    .split(|b| *b == 0)
+                    .take(count)
+                    .map(utf8)
+                    .collect::<Option<Vec<_>>>()?;
+
+                (strings.len() == count).then_some(strings)
+            }
+            _ => None,
+        }
+    }
+
+    fn integers(&self, tag: u32) -> Option<Vec<u64>> {
+        let (ty, data, count) = self.find(tag)?;
+
+        let len = match ty {
+            TYPE_INT16 => 2,
+            TYPE_INT32 => 4,
+            TYPE_INT64 => 8,
+            _ => return None,
+        };
+
+        let data = data.get(..count.checked_mul(len)?)?;
+
+        Some(
+            data.chunks_exact(len)
+                .map(|bytes| {
+                    bytes
+                        .iter()
+                        .fold(0, |acc, byte| (acc << 8) | u64::from(*byte))
+                })
+                .collect(),
+        )
+    }
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn utf8(bytes: &[u8]) -> Option<&str> {
+    str::from_utf8(bytes).ok()
+}
+
+fn next_multiple_of_four(n: usize) -> usize {
+    n + (4 - n % 4) % 4
+}
+
+/// Serializes an [`io::Error`] as its message.
+#[cfg(feature = "serde")]
+fn serialize_io_error<S: serde::Serializer>(
+    e: &io::Error,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(e)
+}
@@ -0,0 +1,102 @@
+use {
+    crate::{Entry, Mode},
+    core::fmt,
+    serde::{ser::SerializeStruct, Serialize, Serializer},
+};
+
+/// The alphabet of the standard Base64 encoding.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl Serialize for Entry<'_> {
+    /// Serializes the metadata of the entry without its content.
+    ///
+    /// Use [`Entry::with_content`] to include the content.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_entry(self, false, serializer)
+    }
+}
+
+/// An [`Entry`] which is serialized with its content encoded in Base64.
+///
+/// This type is returned by [`Entry::with_content`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WithContent<'a>(pub(crate) Entry<'a>);
+impl Serialize for WithContent<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_entry(&self.0, true, serializer)
+    }
+}
+
+impl Serialize for Mode {
+    /// Serializes the mode as a structure with the numeric form `bits` and the symbolic form
+    /// `symbolic` of `ls -l`, such as `{"bits": 33188, "symbolic": "-rw-r--r--"}`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut mode = serializer.serialize_struct("Mode", 2)?;
+
+        mode.serialize_field("bits", &self.bits())?;
+        mode.serialize_field("symbolic", &Collect(self))?;
+        mode.end()
+    }
+}
+
+fn serialize_entry<S: Serializer>(
+    entry: &Entry<'_>,
+    content: bool,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct("Entry", 13 + usize::from(content))?;
+
+    state.serialize_field("name", entry.name())?;
+    state.serialize_field("format", &entry.format())?;
+    state.serialize_field("mode", &entry.mode())?;
+    state.serialize_field("ino", &entry.ino())?;
+    state.serialize_field("uid", &entry.uid())?;
+    state.serialize_field("gid", &entry.gid())?;
+    state.serialize_field("nlink", &entry.nlink())?;
+    state.serialize_field("mtime", &entry.mtime())?;
+    state.serialize_field("device", &entry.device())?;
+    state.serialize_field("rdevice", &entry.rdevice())?;
+    state.serialize_field("size", &entry.size())?;
+    state.serialize_field("check", &entry.check())?;
+    state.serialize_field("symlink_target", &entry.symlink_target())?;
+
+    if content {
+        state.serialize_field("content", &Collect(Base64(entry.file())))?;
+    }
+
+    state.end()
+}
+
+/// Serializes a value which implements [`fmt::Display`] as a string without allocating.
+struct Collect<T>(T);
+impl<T: fmt::Display> Serialize for Collect<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// Displays bytes in the standard Base64 encoding with padding.
+struct Base64<'a>(&'a [u8]);
+impl fmt::Display for Base64<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.0.chunks(3) {
+            let bytes = [
+                chunk[0],
+                chunk.get(1).copied().unwrap_or(0),
+                chunk.get(2).copied().unwrap_or(0),
+            ];
+            let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+            let mut encoded = [b'='; 4];
+
+            for (i, c) in encoded.iter_mut().take(chunk.len() + 1).enumerate() {
+                *c = BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize];
+            }
+
+            f.write_str(core::str::from_utf8(&encoded).map_err(|_| fmt::Error)?)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use {
+    crate::{Entry, Iter},
+    core::{convert::TryInto, fmt},
+};
+
+/// The magic string which ends a cpio file with an appended signature, which is the same as that
+/// of the Linux kernel modules.
+const APPENDED_SIGNATURE_MAGIC: &[u8] = b"~Module signature appended~\n";
+/// The length of `struct module_signature` preceding [`APPENDED_SIGNATURE_MAGIC`].
+const MODULE_SIGNATURE_LEN: usize = 12;
+
+/// A cpio file whose signature is verified.
+///
+/// This type can only be created by verifying a signature, so a function taking it never reads
+/// the entries of a tampered cpio file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VerifiedArchive<'a>(&'a [u8]);
+impl<'a> VerifiedArchive<'a> {
+    /// Verifies the detached Ed25519 signature of `cpio_binary` with `public_key`.
+    ///
+    /// The signature is verified with the strict rules of `ed25519-dalek`, which reject weak
+    /// public keys and malleable signatures.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if `signature` or `public_key` is malformed, or if the
+    /// signature does not match.
+    #[cfg(feature = "ed25519")]
+    pub fn verify_ed25519(
+        cpio_binary: &'a [u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<Self, SignatureError> {
+        let signature = ed25519_dalek::Signature::from_slice(signature)
+            .map_err(|_| SignatureError::MalformedSignature)?;
+        let public_key = public_key
+            .try_into()
+            .ok()
+            .and_then(|key| ed25519_dalek::VerifyingKey::from_bytes(key).ok())
+            .ok_or(SignatureError::MalformedKey)?;
+
+        public_key
+            .verify_strict(cpio_binary, &signature)
+            .map(|()| Self(cpio_binary))
+            .map_err(|_| SignatureError::Mismatch)
+    }
+
+    /// Verifies `cpio_binary` with the given function, which returns `true` if the signature is
+    /// valid.
+    ///
+    /// Use this method for the signature schemes which this crate does not implement, such as the
+    /// PKCS#7 signatures split by [`AppendedSignature::split`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`SignatureError::Mismatch`] if `verify` returns `false`.
+    pub fn verify_with(
+        cpio_binary: &'a [u8],
+        verify: impl FnOnce(&[u8]) -> bool,
+    ) -> Result<Self, SignatureError> {
+        if verify(cpio_binary) {
+            Ok(Self(cpio_binary))
+        } else {
+            Err(SignatureError::Mismatch)
+        }
+    }
+
+    /// Returns the verified cpio file.
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Returns an iterator over the entries of the verified cpio file.
+    ///
+    /// See [`iter_files`](crate::iter_files).
+    pub fn iter_files(&self) -> impl Iterator<Item = Entry<'a>> {
+        Iter::new(self.0)
+    }
+}
+
+/// A signature appended to a cpio file in the same layout as the Linux kernel modules.
+///
+/// The signed file consists of the cpio file, the signature, `struct module_signature` and the
+/// magic string `~Module signature appended~\n`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AppendedSignature<'a> {
+    signature: &'a [u8],
+    algorithm: u8,
+    hash: u8,
+    id_type: u8,
+}
+impl<'a> AppendedSignature<'a> {
+    /// The identifier type of PKCS#7 signatures, which the Linux kernel uses.
+    pub const PKCS7: u8 = 2;
+
+    /// Splits `signed` into the cpio file and the appended signature.
+    ///
+    /// This method returns [`None`] if `signed` does not end with an appended signature.
+    #[must_use]
+    pub fn split(signed: &'a [u8]) -> Option<(&'a [u8], Self)> {
+        let rest = signed.strip_suffix(APPENDED_SIGNATURE_MAGIC)?;
+        let header_offset = rest.len().checked_sub(MODULE_SIGNATURE_LEN)?;
+        let (rest, header) = rest.split_at(header_offset);
+
+        let signature_len = u32::from_be_bytes(header[8..12].try_into().ok()?);
+        let signature_offset = rest.len().checked_sub(signature_len.try_into().ok()?)?;
+        let (cpio_binary, signature) = rest.split_at(signature_offset);
+
+        Some((
+            cpio_binary,
+            Self {
+                signature,
+                algorithm: header[0],
+                hash: header[1],
+                id_type: header[2],
+            },
+        ))
+    }
+
+    /// Returns the signature.
+    #[must_use]
+    pub fn signature(&self) -> &'a [u8] {
+        self.signature
+    }
+
+    /// Returns the `algo` field of `struct module_signature`.
+    #[must_use]
+    pub fn algorithm(&self) -> u8 {
+        self.algorithm
+    }
+
+    /// Returns the `hash` field of `struct module_signature`.
+    #[must_use]
+    pub fn hash(&self) -> u8 {
+        self.hash
+    }
+
+    /// Returns the `id_type` field of `struct module_signature`, which is
+    /// [`AppendedSignature::PKCS7`] for the signatures made by the Linux kernel tools.
+    #[must_use]
+    pub fn id_type(&self) -> u8 {
+        self.id_type
+    }
+}
+
+/// An error which occurs while verifying the signature of a cpio file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SignatureError {
+    /// The signature is malformed.
+    MalformedSignature,
+    /// The public key is malformed.
+    MalformedKey,
+    /// The signature does not match the cpio file.
+    Mismatch,
+}
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedSignature => f.write_str("the signature is malformed"),
+            Self::MalformedKey => f.write_str("the public key is malformed"),
+            Self::Mismatch => f.write_str("the signature does not match the cpio file"),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for SignatureError {}
@@ -0,0 +1,141 @@
+use {
+    crate::{Component, Entry, EntryPath, Iter, Mode, FILE_TYPE_MASK},
+    alloc::{collections::BTreeMap, string::String, vec::Vec},
+    core::fmt,
+};
+
+/// The maximum number of symbolic links followed in a resolution, which is the same as that of
+/// Linux.
+const MAX_SYMLINKS: usize = 40;
+
+/// A resolver of the symbolic links in a cpio file.
+///
+/// The symbolic links are resolved within the cpio file as if it were extracted to the root
+/// directory. The absolute targets start from the root of the cpio file, and the relative ones
+/// from the directory containing the link. The symbolic links in the directories of the path are
+/// also followed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SymlinkResolver<'a> {
+    entries: BTreeMap<EntryPath<'a>, Entry<'a>>,
+}
+impl<'a> SymlinkResolver<'a> {
+    /// Reads the entries of the given cpio file.
+    ///
+    /// If multiple entries have the same path, the last one is used as extracting the cpio file
+    /// leaves it. The entries after a corrupt one are ignored.
+    #[must_use]
+    pub fn new(cpio_binary: &'a [u8]) -> Self {
+        Self {
+            entries: Iter::new(cpio_binary)
+                .map(|entry| (entry.path(), entry))
+                .collect(),
+        }
+    }
+
+    /// Returns the entry whose path is `name` without following the symbolic links.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Entry<'a>> {
+        self.entries.get(&EntryPath::new(name)).copied()
+    }
+
+    /// Returns the entry which `name` finally refers to, following the symbolic links.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the entry is not found, if a symbolic link has a non-UTF-8
+    /// target, or if more than 40 symbolic links are followed, which usually means a loop.
+    pub fn resolve(&self, name: &str) -> Result<Entry<'a>, ResolveError> {
+        let mut resolved = Vec::new();
+        let mut pending: Vec<_> = EntryPath::new(name).components().rev().collect();
+        let mut followed = 0;
+        let mut buffer = String::new();
+
+        while let Some(component) = pending.pop() {
+            match component {
+                Component::ParentDir => {
+                    resolved.pop();
+
+                    continue;
+                }
+                Component::Normal(name) => resolved.push(name),
+            }
+
+            let entry = match self.lookup(&resolved, &mut buffer) {
+                Some(entry)
+                    if entry.mode().bits() & FILE_TYPE_MASK == Mode::SYMBOLIK_LINK.bits() =>
+                {
+                    entry
+                }
+                _ => continue,
+            };
+
+            let offset = entry.header_offset();
+
+            let target = entry
+                .symlink_target()
+                .ok_or(ResolveError::InvalidTarget { offset })?;
+
+            followed += 1;
+
+            if followed > MAX_SYMLINKS {
+                return Err(ResolveError::TooManySymlinks { offset });
+            }
+
+            resolved.pop();
+
+            if EntryPath::new(target).is_absolute() {
+                resolved.clear();
+            }
+
+            pending.extend(EntryPath::new(target).components().rev());
+        }
+
+        self.lookup(&resolved, &mut buffer)
+            .ok_or(ResolveError::NotFound)
+    }
+
+    fn lookup(&self, components: &[&str], buffer: &mut String) -> Option<Entry<'a>> {
+        buffer.clear();
+
+        for component in components {
+            buffer.push('/');
+            buffer.push_str(component);
+        }
+
+        self.get(buffer)
+    }
+}
+
+/// An error which occurs while resolving a symbolic link.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ResolveError {
+    /// The entry is not found.
+    NotFound,
+    /// The target of a symbolic link is not valid UTF-8.
+    InvalidTarget {
+        /// The offset of the symbolic link from the start of the cpio file.
+        offset: usize,
+    },
+    /// Too many symbolic links are followed, which usually means a loop.
+    TooManySymlinks {
+        /// The offset of the last followed symbolic link from the start of the cpio file.
+        offset: usize,
+    },
+}
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => f.write_str("the entry is not found"),
+            Self::InvalidTarget { offset } => write!(
+                f,
+                "the target of the symbolic link at offset {offset} is not valid UTF-8"
+            ),
+            Self::TooManySymlinks { offset } => {
+                write!(f, "too many symbolic links are followed at offset {offset}")
+            }
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for ResolveError {}
@@ -0,0 +1,436 @@
+use {
+    crate::{
+        write::{self, Counter, Write, WriteError},
+        DeviceEncoding, DeviceNumber, Entry, EntryBuilder, EntryPath, Format, IoWriter, Iter, Mode,
+        FILE_TYPE_MASK, PERMISSIONS_MASK, TRAILER_NAME,
+    },
+    core::convert::TryFrom,
+    std::{
+        borrow::ToOwned,
+        collections::{hash_map, HashMap},
+        format,
+        io::{self, Read},
+        string::String,
+        vec::Vec,
+    },
+    tar::{Archive, Builder, EntryType, Header},
+};
+
+/// The length of the `name` and the `linkname` fields of a ustar header.
+const NAME_FIELD_LEN: usize = 100;
+/// The largest value of the 8-byte numeric fields of a ustar header.
+const MAX_ID: u64 = 0o777_7777;
+/// The largest value of the 12-byte numeric fields of a ustar header.
+const MAX_TIME: u64 = 0o777_7777_7777;
+
+/// Converts the given cpio file into a tar file and writes it to `tar`.
+///
+/// The names and the symbolic link targets longer than 100 bytes and the numeric fields which do
+/// not fit in a ustar header are stored in pax extended headers. The regular files sharing an
+/// inode number are converted into hard links to the first of them. Sockets are skipped because
+/// tar cannot represent them.
+///
+/// This function returns `tar` after writing the end-of-archive marker.
+///
+/// # Errors
+///
+/// This function returns an error if `cpio` is corrupt, if a device number cannot be represented
+/// in a ustar header, or if writing to `tar` fails.
+pub fn cpio_to_tar<W: io::Write>(cpio: &[u8], tar: W) -> io::Result<W> {
+    let mut iter = Iter::new(cpio);
+
+    let entries: Vec<_> = iter.by_ref().collect();
+
+    iter.trailer()
+        .map_err(|offset| invalid_data(format!("the cpio file is corrupt at offset {offset}")))?;
+
+    // New ASCII Format and New CRC Format store the content of hard links only in one of them.
+    let mut contents = HashMap::new();
+
+    for entry in entries.iter().filter(|entry| is_hard_link(entry)) {
+        let content = contents.entry(link_key(entry)).or_insert(entry.file());
+
+        if !entry.file().is_empty() {
+            *content = entry.file();
+        }
+    }
+
+    let mut first_names = HashMap::new();
+    let mut builder = Builder::new(tar);
+
+    for entry in &entries {
+        let (entry_type, mut data) = match entry.mode().bits() & FILE_TYPE_MASK {
+            S_IFREG => (EntryType::Regular, entry.file()),
+            S_IFDIR => (EntryType::Directory, &[][..]),
+            S_IFLNK => (EntryType::Symlink, &[][..]),
+            S_IFCHR => (EntryType::Char, &[][..]),
+            S_IFBLK => (EntryType::Block, &[][..]),
+            S_IFIFO => (EntryType::Fifo, &[][..]),
+            _ => continue,
+        };
+
+        let mut header = Header::new_ustar();
+        let mut pax = Vec::new();
+
+        header.set_entry_type(entry_type);
+
+        set_name(
+            &mut header.as_old_mut().name,
+            "path",
+            entry.name().as_bytes(),
+            &mut pax,
+        );
+
+        if entry_type == EntryType::Symlink {
+            set_name(
+                &mut header.as_old_mut().linkname,
+                "linkpath",
+                entry.file(),
+                &mut pax,
+            );
+        }
+
+        if is_hard_link(entry) {
+            match first_names.entry(link_key(entry)) {
+                hash_map::Entry::Occupied(first) => {
+                    let first: &&str = first.get();
+
+                    header.set_entry_type(EntryType::Link);
+                    set_name(
+                        &mut header.as_old_mut().linkname,
+                        "linkpath",
+                        first.as_bytes(),
+                        &mut pax,
+                    );
+
+                    data = &[];
+                }
+                hash_map::Entry::Vacant(first) => {
+                    first.insert(entry.name());
+
+                    data = contents[&link_key(entry)];
+                }
+            }
+        }
+
+        let rdevice = match entry_type {
+            EntryType::Char | EntryType::Block => entry.rdevice(),
+            _ => DeviceNumber::default(),
+        };
+
+        header.set_device_major(rdevice.major)?;
+        header.set_device_minor(rdevice.minor)?;
+
+        header.set_mode(entry.mode().bits() & PERMISSIONS_MASK);
+        header.set_uid(numeric_field("uid", entry.uid().into(), MAX_ID, &mut pax));
+        header.set_gid(numeric_field("gid", entry.gid().into(), MAX_ID, &mut pax));
+        header.set_mtime(numeric_field("mtime", entry.mtime(), MAX_TIME, &mut pax));
+        header.set_size(u64::try_from(data.len()).unwrap_or(u64::MAX));
+
+        if !pax.is_empty() {
+            builder.append_pax_extensions(pax.iter().map(|(key, value)| (*key, &value[..])))?;
+        }
+
+        header.set_cksum();
+        builder.append(&header, data)?;
+    }
+
+    builder.into_inner()
+}
+
+/// Converts the given tar file into a cpio file of `format` and writes it to `cpio`.
+///
+/// The entries are numbered with sequential inode numbers, and hard links share the inode number
+/// of their targets. The content of hard links is stored in every entry for Old Binary Format and
+/// Portable ASCII Format, and only in the last one for New ASCII Format and New CRC Format as GNU
+/// cpio does. The output is padded with zeros to a multiple of 512 bytes.
+///
+/// This function returns `cpio` after writing the trailer.
+///
+/// # Errors
+///
+/// This function returns an error if reading `tar` fails, if `tar` contains an entry which cannot
+/// be represented in `format` (e.g., a non-UTF-8 name or an unsupported entry type), or if writing
+/// to `cpio` fails.
+pub fn tar_to_cpio<R: Read, W: io::Write>(tar: R, format: Format, cpio: W) -> io::Result<W> {
+    let records = read_tar(tar)?;
+
+    // The index of the record which has the content of each record. Hard links point to their
+    // targets, and the others to themselves.
+    let mut targets = HashMap::new();
+    let mut origins = Vec::with_capacity(records.len());
+
+    for (i, record) in records.iter().enumerate() {
+        let origin = match &record.link {
+            Some(target) => *targets.get(&EntryPath::new(target)).ok_or_else(|| {
+                invalid_data(format!(
+                    "the target of the hard link `{}` is not found",
+                    record.name
+                ))
+            })?,
+            None => i,
+        };
+
+        targets
+            .entry(EntryPath::new(&record.name))
+            .or_insert(origin);
+        origins.push(origin);
+    }
+
+    let mut nlinks = HashMap::new();
+
+    for origin in &origins {
+        *nlinks.entry(*origin).or_insert(0) += 1;
+    }
+
+    let mut cpio = IoWriter(cpio);
+    let mut sink = Counter::new(&mut cpio);
+
+    for (i, (record, origin)) in records.iter().zip(origins.iter().copied()).enumerate() {
+        let ino = u32::try_from(origin + 1).map_err(|_| unrepresentable("ino", &record.name))?;
+
+        let has_content = match format {
+            Format::OldBinary(_) | Format::PortableAscii => true,
+            Format::NewAscii | Format::NewCrc => !origins[i + 1..].contains(&origin),
+        };
+
+        let data = &records[origin].data;
+
+        let nlink = if record.file_type == S_IFDIR {
+            2
+        } else {
+            nlinks[&origin]
+        };
+
+        let mut builder = EntryBuilder::new(&record.name, format)
+            .ino(ino)
+            .mode(Mode::from_bits_truncate(record.file_type | record.mode))
+            .uid(narrow(record.uid, "uid", &record.name)?)
+            .gid(narrow(record.gid, "gid", &record.name)?)
+            .nlink(nlink)
+            .mtime(record.mtime)
+            .file(if has_content { data } else { &[] });
+
+        builder = match format {
+            Format::OldBinary(_) | Format::PortableAscii => {
+                let rdev = DeviceEncoding::Linux
+                    .encode(record.devmajor, record.devminor)
+                    .ok_or_else(|| unrepresentable("rdev", &record.name))?;
+
+                builder.rdev(rdev)
+            }
+            Format::NewAscii | Format::NewCrc => builder
+                .rdevmajor(record.devmajor)
+                .rdevminor(record.devminor),
+        };
+
+        builder
+            .build()
+            .write_to(&mut sink)
+            .map_err(|e| write_error(e, &record.name))?;
+    }
+
+    write::write_trailer(format, &mut sink).map_err(|e| write_error(e, TRAILER_NAME))?;
+
+    let padding = (512 - sink.written % 512) % 512;
+
+    sink.write_all(&[0; 512][..padding])?;
+
+    Ok(cpio.0)
+}
+
+const S_IFREG: u32 = Mode::REGULAR_FILE.bits();
+const S_IFDIR: u32 = Mode::DIRECTORY.bits();
+const S_IFLNK: u32 = Mode::SYMBOLIK_LINK.bits();
+const S_IFCHR: u32 = Mode::CHARACTER_SPECIAL_DEVICE.bits();
+const S_IFBLK: u32 = Mode::BLOCK_SPECIAL_DEVICE.bits();
+const S_IFIFO: u32 = Mode::NAMED_PIPE_FIFO.bits();
+
+/// An entry of a tar file whose content is read into memory.
+struct Record {
+    name: String,
+    /// The target of a hard link.
+    link: Option<String>,
+    file_type: u32,
+    mode: u32,
+    uid: u64,
+    gid: u64,
+    mtime: u64,
+    devmajor: u32,
+    devminor: u32,
+    data: Vec<u8>,
+}
+
+fn read_tar<R: Read>(tar: R) -> io::Result<Vec<Record>> {
+    let mut archive = Archive::new(tar);
+    let mut records = Vec::new();
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+
+        let header = entry.header();
+
+        let file_type = match header.entry_type() {
+            EntryType::Regular | EntryType::Continuous | EntryType::Link => S_IFREG,
+            EntryType::Directory => S_IFDIR,
+            EntryType::Symlink => S_IFLNK,
+            EntryType::Char => S_IFCHR,
+            EntryType::Block => S_IFBLK,
+            EntryType::Fifo => S_IFIFO,
+            EntryType::XGlobalHeader => continue,
+            entry_type => {
+                return Err(invalid_data(format!(
+                    "unsupported tar entry type {}",
+                    entry_type.as_byte()
+                )))
+            }
+        };
+        let is_hard_link = header.entry_type() == EntryType::Link;
+
+        let mode = header.mode()? & PERMISSIONS_MASK;
+        let mut uid = header.uid()?;
+        let mut gid = header.gid()?;
+        let mut mtime = header.mtime()?;
+        // Some tools leave the device fields of the other entries blank.
+        let (devmajor, devminor) = if file_type == S_IFCHR || file_type == S_IFBLK {
+            (
+                header.device_major()?.unwrap_or(0),
+                header.device_minor()?.unwrap_or(0),
+            )
+        } else {
+            (0, 0)
+        };
+
+        let path = utf8(entry.path_bytes().into_owned())?;
+        let name = match path.trim_end_matches('/') {
+            "" => path.clone(),
+            trimmed => trimmed.to_owned(),
+        };
+
+        let link_name = entry
+            .link_name_bytes()
+            .map(|link_name| utf8(link_name.into_owned()))
+            .transpose()?;
+
+        if let Some(extensions) = entry.pax_extensions()? {
+            for extension in extensions {
+                let extension = extension?;
+
+                let field = match extension.key() {
+                    Ok("uid") => &mut uid,
+                    Ok("gid") => &mut gid,
+                    Ok("mtime") => &mut mtime,
+                    _ => continue,
+                };
+
+                // `mtime` may have a fractional part, which cpio cannot represent.
+                let value = extension.value().ok().and_then(|value| {
+                    value
+                        .split('.')
+                        .next()
+                        .and_then(|integer| integer.parse().ok())
+                });
+
+                *field = value.ok_or_else(|| {
+                    invalid_data(format!("invalid pax extended header of `{name}`"))
+                })?;
+            }
+        }
+
+        let (link, data) = if is_hard_link {
+            let target = link_name
+                .ok_or_else(|| invalid_data(format!("the hard link `{name}` has no target")))?;
+
+            (Some(target.trim_end_matches('/').to_owned()), Vec::new())
+        } else if file_type == S_IFLNK {
+            (None, link_name.unwrap_or_default().into_bytes())
+        } else {
+            let mut data = Vec::new();
+
+            entry.read_to_end(&mut data)?;
+
+            (None, data)
+        };
+
+        records.push(Record {
+            name,
+            link,
+            file_type,
+            mode,
+            uid,
+            gid,
+            mtime,
+            devmajor,
+            devminor,
+            data,
+        });
+    }
+
+    Ok(records)
+}
+
+/// Writes `value` to the `name` or the `linkname` field, adding a pax record if it does not fit.
+fn set_name(
+    field: &mut [u8; NAME_FIELD_LEN],
+    key: &'static str,
+    value: &[u8],
+    pax: &mut Vec<(&'static str, Vec<u8>)>,
+) {
+    let len = value.len().min(NAME_FIELD_LEN);
+
+    field.fill(0);
+    field[..len].copy_from_slice(&value[..len]);
+
+    if value.len() > NAME_FIELD_LEN {
+        pax.push((key, value.to_owned()));
+    }
+}
+
+/// Returns the value to write to a numeric field, adding a pax record if `value` does not fit.
+fn numeric_field(
+    key: &'static str,
+    value: u64,
+    max: u64,
+    pax: &mut Vec<(&'static str, Vec<u8>)>,
+) -> u64 {
+    if value > max {
+        pax.push((key, format!("{value}").into_bytes()));
+
+        0
+    } else {
+        value
+    }
+}
+
+fn is_hard_link(entry: &Entry<'_>) -> bool {
+    entry.mode().bits() & FILE_TYPE_MASK == S_IFREG && entry.nlink() > 1
+}
+
+fn link_key(entry: &Entry<'_>) -> (Option<u32>, Option<u32>, Option<u32>, u32) {
+    (entry.dev(), entry.devmajor(), entry.devminor(), entry.ino())
+}
+
+fn narrow(value: u64, field: &'static str, name: &str) -> io::Result<u32> {
+    u32::try_from(value).map_err(|_| unrepresentable(field, name))
+}
+
+fn utf8(bytes: Vec<u8>) -> io::Result<String> {
+    String::from_utf8(bytes).map_err(|e| invalid_data(format!("non-UTF-8 name: {e}")))
+}
+
+fn write_error(e: WriteError<io::Error>, name: &str) -> io::Error {
+    match e {
+        WriteError::Sink(e) => e,
+        WriteError::Unrepresentable(field) => unrepresentable(field, name),
+    }
+}
+
+fn unrepresentable(field: &str, name: &str) -> io::Error {
+    invalid_data(format!(
+        "the value of `{field}` of `{name}` cannot be represented"
+    ))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
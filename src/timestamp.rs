@@ -0,0 +1,160 @@
+use core::{convert::TryFrom, fmt};
+
+/// The number of seconds in a day.
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+/// The abbreviated names of the months, as `ls` and cpio show them.
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// A modification time, in seconds since the Unix epoch.
+///
+/// This type is displayed in RFC 3339 format in UTC, such as `2021-08-23T12:34:56Z`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Timestamp(u64);
+impl Timestamp {
+    /// The Unix epoch.
+    pub const UNIX_EPOCH: Self = Self(0);
+
+    /// Creates a new timestamp from the seconds since the Unix epoch.
+    #[must_use]
+    pub fn from_secs(secs: u64) -> Self {
+        Self(secs)
+    }
+
+    /// Returns the seconds since the Unix epoch.
+    #[must_use]
+    pub fn as_secs(self) -> u64 {
+        self.0
+    }
+
+    /// Reinterprets the timestamp as the lowest `bits` bits of a time which has wrapped around,
+    /// and returns the time closest to `reference`.
+    ///
+    /// The formats store the modification time in a limited number of bits, and the 32-bit
+    /// fields of Old Binary Format, New ASCII Format and New CRC Format overflow in 2106. Some
+    /// archivers store only the lowest bits of later times. Passing the current time as
+    /// `reference` recovers such times. The timestamp is returned as it is if `bits` is 64 or
+    /// more, or if the timestamp does not fit in `bits` bits.
+    #[must_use]
+    pub fn nearest_wrapped(self, bits: u32, reference: Timestamp) -> Self {
+        let modulus = match 1_u64.checked_shl(bits) {
+            Some(modulus) if bits < 64 && self.0 < modulus => modulus,
+            _ => return self,
+        };
+
+        let candidate = (reference.0 & !(modulus - 1)) | self.0;
+
+        [
+            candidate.checked_sub(modulus),
+            Some(candidate),
+            candidate.checked_add(modulus),
+        ]
+        .iter()
+        .flatten()
+        .min_by_key(|time| time.abs_diff(reference.0))
+        .map_or(self, |time| Self(*time))
+    }
+
+    /// Returns the timestamp as a [`std::time::SystemTime`], or [`None`] if it cannot be
+    /// represented.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn to_system_time(self) -> Option<std::time::SystemTime> {
+        std::time::UNIX_EPOCH.checked_add(core::time::Duration::from_secs(self.0))
+    }
+
+    /// Returns the timestamp as a [`chrono::DateTime`] in UTC, or [`None`] if it is out of range.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn to_chrono(self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp(i64::try_from(self.0).ok()?, 0)
+    }
+
+    /// Returns the timestamp as a [`time::OffsetDateTime`] in UTC, or [`None`] if it is out of
+    /// range.
+    #[cfg(feature = "time")]
+    #[must_use]
+    pub fn to_offset_date_time(self) -> Option<time::OffsetDateTime> {
+        time::OffsetDateTime::from_unix_timestamp(i64::try_from(self.0).ok()?).ok()
+    }
+
+    /// Returns an object which displays the timestamp in UTC as `ls -l` and `cpio -tv` do.
+    ///
+    /// The time of day is shown as `Aug 23 12:34` if the timestamp is within six months before
+    /// `now`, and the year is shown as `Aug 23  2021` otherwise.
+    #[must_use]
+    pub fn display_ls(self, now: Timestamp) -> impl fmt::Display {
+        LsFormat {
+            timestamp: self,
+            now,
+        }
+    }
+
+    /// Returns the year, the month and the day in the proleptic Gregorian calendar.
+    fn date(self) -> (u64, usize, u64) {
+        // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days.
+        let z = self.0 / SECS_PER_DAY + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = era * 400 + yoe + u64::from(month <= 2);
+
+        (year, usize::try_from(month).unwrap_or(1), day)
+    }
+
+    /// Returns the hour, the minute and the second.
+    fn time_of_day(self) -> (u64, u64, u64) {
+        let secs = self.0 % SECS_PER_DAY;
+
+        (secs / 3600, secs % 3600 / 60, secs % 60)
+    }
+}
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.date();
+        let (hour, minute, second) = self.time_of_day();
+
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z"
+        )
+    }
+}
+impl From<u64> for Timestamp {
+    fn from(secs: u64) -> Self {
+        Self(secs)
+    }
+}
+impl From<Timestamp> for u64 {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+struct LsFormat {
+    timestamp: Timestamp,
+    now: Timestamp,
+}
+impl fmt::Display for LsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const SIX_MONTHS: u64 = 6 * 30 * SECS_PER_DAY;
+
+        let (year, month, day) = self.timestamp.date();
+        let month = MONTHS[month - 1];
+
+        match self.now.0.checked_sub(self.timestamp.0) {
+            Some(elapsed) if elapsed <= SIX_MONTHS => {
+                let (hour, minute, _) = self.timestamp.time_of_day();
+
+                write!(f, "{month} {day:2} {hour:02}:{minute:02}")
+            }
+            _ => write!(f, "{month} {day:2}  {year}"),
+        }
+    }
+}
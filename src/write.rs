@@ -0,0 +1,318 @@
+use {
+    crate::{Endianness, Entry, EntryBuilder, Format, TRAILER_NAME},
+    core::{convert::TryFrom, fmt},
+};
+#[cfg(feature = "alloc")]
+use {alloc::vec::Vec, core::convert::Infallible};
+
+/// A sink to which cpio files are written.
+pub trait Write {
+    /// The type of errors returned when writing fails.
+    type Error;
+
+    /// Writes all of the given bytes to the sink.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the bytes could not be written.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+impl<W: Write + ?Sized> Write for &mut W {
+    type Error = W::Error;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).write_all(bytes)
+    }
+}
+#[cfg(feature = "alloc")]
+impl Write for Vec<u8> {
+    type Error = Infallible;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.extend_from_slice(bytes);
+
+        Ok(())
+    }
+}
+
+/// A sink which counts the written bytes to pad the output to a multiple of a block size.
+pub(crate) struct Counter<'a, W: ?Sized> {
+    sink: &'a mut W,
+    pub(crate) written: usize,
+}
+impl<'a, W: Write + ?Sized> Counter<'a, W> {
+    pub(crate) fn new(sink: &'a mut W) -> Self {
+        Self { sink, written: 0 }
+    }
+}
+impl<W: Write + ?Sized> Write for Counter<'_, W> {
+    type Error = W::Error;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.sink.write_all(bytes)?;
+        self.written += bytes.len();
+
+        Ok(())
+    }
+}
+
+/// An adapter to use a [`std::io::Write`] as a [`Write`].
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IoWriter<W>(pub W);
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Write for IoWriter<W> {
+    type Error = std::io::Error;
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.0.write_all(bytes)
+    }
+}
+
+/// An error which occurs while writing a cpio file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum WriteError<E> {
+    /// The sink returned an error.
+    Sink(E),
+    /// The value of the named header field cannot be represented in the format.
+    Unrepresentable(&'static str),
+}
+impl<E> From<E> for WriteError<E> {
+    fn from(e: E) -> Self {
+        Self::Sink(e)
+    }
+}
+impl<E: fmt::Display> fmt::Display for WriteError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sink(e) => write!(f, "failed to write to the sink: {e}"),
+            Self::Unrepresentable(field) => {
+                write!(f, "the value of `{field}` cannot be represented")
+            }
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for WriteError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Sink(e) => Some(e),
+            Self::Unrepresentable(_) => None,
+        }
+    }
+}
+
+pub(crate) fn write_entry<W: Write + ?Sized>(
+    entry: &Entry<'_>,
+    format: Format,
+    sink: &mut W,
+) -> Result<(), WriteError<W::Error>> {
+    match format {
+        Format::OldBinary(endianness) => write_as_old_binary(entry, endianness, sink),
+        Format::PortableAscii => write_as_portable_ascii(entry, sink),
+        Format::NewAscii | Format::NewCrc => write_as_new_ascii_or_crc(entry, format, sink),
+    }
+}
+
+/// Writes the trailer of a cpio file of the given format to `sink`.
+///
+/// The trailer must follow the last entry written by [`Entry::write_to`]. Note that some tools,
+/// such as GNU cpio, additionally pad the cpio file with zeros to a multiple of 512 bytes.
+///
+/// # Errors
+///
+/// This function returns an error if `sink` fails.
+pub fn write_trailer<W: Write + ?Sized>(
+    format: Format,
+    sink: &mut W,
+) -> Result<(), WriteError<W::Error>> {
+    write_entry(
+        &EntryBuilder::new(TRAILER_NAME, format).build(),
+        format,
+        sink,
+    )
+}
+
+fn write_as_old_binary<W: Write + ?Sized>(
+    entry: &Entry<'_>,
+    endianness: Endianness,
+    sink: &mut W,
+) -> Result<(), WriteError<W::Error>> {
+    const MAGIC: u16 = 0o070_707;
+
+    let namesize = namesize(entry);
+    let filesize = entry.file().len();
+
+    let [mtime_most, mtime_least] = split_into_u16s(narrow(entry.mtime(), "mtime")?);
+    let [filesize_most, filesize_least] = split_into_u16s(narrow(filesize, "filesize")?);
+
+    let fields = [
+        MAGIC,
+        narrow(entry.dev().unwrap_or(0), "dev")?,
+        narrow(entry.ino(), "ino")?,
+        narrow(entry.mode().bits(), "mode")?,
+        narrow(entry.uid(), "uid")?,
+        narrow(entry.gid(), "gid")?,
+        narrow(entry.nlink(), "nlink")?,
+        narrow(entry.rdev().unwrap_or(0), "rdev")?,
+        mtime_most,
+        mtime_least,
+        narrow(namesize, "namesize")?,
+        filesize_most,
+        filesize_least,
+    ];
+
+    let mut header = [0; 26];
+
+    for (bytes, field) in header.chunks_exact_mut(2).zip(fields.iter()) {
+        bytes.copy_from_slice(&endianness.u16_to_u8_array(*field));
+    }
+
+    sink.write_all(&header)?;
+    write_name(entry, sink)?;
+    write_padding(namesize % 2, sink)?;
+    sink.write_all(entry.file())?;
+    write_padding(filesize % 2, sink)
+}
+
+fn write_as_portable_ascii<W: Write + ?Sized>(
+    entry: &Entry<'_>,
+    sink: &mut W,
+) -> Result<(), WriteError<W::Error>> {
+    let mut header = [0; 76];
+
+    header[..6].copy_from_slice(b"070707");
+
+    let fields = [
+        (6, entry.dev().unwrap_or(0).into(), "dev"),
+        (6, entry.ino().into(), "ino"),
+        (6, entry.mode().bits().into(), "mode"),
+        (6, entry.uid().into(), "uid"),
+        (6, entry.gid().into(), "gid"),
+        (6, entry.nlink().into(), "nlink"),
+        (6, entry.rdev().unwrap_or(0).into(), "rdev"),
+        (11, entry.mtime(), "mtime"),
+        (6, widen(namesize(entry)), "namesize"),
+        (11, widen(entry.file().len()), "filesize"),
+    ];
+
+    let mut current = 6;
+
+    for (len, value, name) in fields.iter().copied() {
+        write_digits(&mut header[current..current + len], value, 8, name)?;
+
+        current += len;
+    }
+
+    sink.write_all(&header)?;
+    write_name(entry, sink)?;
+    sink.write_all(entry.file())?;
+
+    Ok(())
+}
+
+fn write_as_new_ascii_or_crc<W: Write + ?Sized>(
+    entry: &Entry<'_>,
+    format: Format,
+    sink: &mut W,
+) -> Result<(), WriteError<W::Error>> {
+    let mut header = [0; 110];
+
+    let check = if format == Format::NewCrc {
+        entry.check().unwrap_or_else(|| entry.computed_checksum())
+    } else {
+        0
+    };
+
+    header[..6].copy_from_slice(if format == Format::NewCrc {
+        b"070702"
+    } else {
+        b"070701"
+    });
+
+    let namesize = namesize(entry);
+    let filesize = entry.file().len();
+
+    let fields = [
+        (entry.ino().into(), "ino"),
+        (entry.mode().bits().into(), "mode"),
+        (entry.uid().into(), "uid"),
+        (entry.gid().into(), "gid"),
+        (entry.nlink().into(), "nlink"),
+        (entry.mtime(), "mtime"),
+        (widen(filesize), "filesize"),
+        (entry.devmajor().unwrap_or(0).into(), "devmajor"),
+        (entry.devminor().unwrap_or(0).into(), "devminor"),
+        (entry.rdevmajor().unwrap_or(0).into(), "rdevmajor"),
+        (entry.rdevminor().unwrap_or(0).into(), "rdevminor"),
+        (widen(namesize), "namesize"),
+        (check.into(), "check"),
+    ];
+
+    for ((value, name), digits) in fields.iter().copied().zip(header[6..].chunks_exact_mut(8)) {
+        write_digits(digits, value, 16, name)?;
+    }
+
+    sink.write_all(&header)?;
+    write_name(entry, sink)?;
+    write_padding((4 - (header.len() + namesize) % 4) % 4, sink)?;
+    sink.write_all(entry.file())?;
+    write_padding((4 - filesize % 4) % 4, sink)
+}
+
+fn write_name<W: Write + ?Sized>(
+    entry: &Entry<'_>,
+    sink: &mut W,
+) -> Result<(), WriteError<W::Error>> {
+    sink.write_all(entry.name().as_bytes())?;
+    sink.write_all(&[0])?;
+
+    Ok(())
+}
+
+fn write_padding<W: Write + ?Sized>(len: usize, sink: &mut W) -> Result<(), WriteError<W::Error>> {
+    sink.write_all(&[0; 4][..len])?;
+
+    Ok(())
+}
+
+pub(crate) fn write_digits<E>(
+    digits: &mut [u8],
+    mut value: u64,
+    radix: u64,
+    field: &'static str,
+) -> Result<(), WriteError<E>> {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    for digit in digits.iter_mut().rev() {
+        *digit = DIGITS[usize::from(u8::try_from(value % radix).unwrap())];
+
+        value /= radix;
+    }
+
+    if value == 0 {
+        Ok(())
+    } else {
+        Err(WriteError::Unrepresentable(field))
+    }
+}
+
+/// Returns the length of the name including the terminating null character.
+fn namesize(entry: &Entry<'_>) -> usize {
+    entry.name().len() + 1
+}
+
+fn narrow<T: TryFrom<U>, U, E>(value: U, field: &'static str) -> Result<T, WriteError<E>> {
+    T::try_from(value).map_err(|_| WriteError::Unrepresentable(field))
+}
+
+fn widen(value: usize) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+fn split_into_u16s(value: u32) -> [u16; 2] {
+    let [a, b, c, d] = value.to_be_bytes();
+
+    [u16::from_be_bytes([a, b]), u16::from_be_bytes([c, d])]
+}
@@ -0,0 +1,118 @@
+#![allow(missing_docs, unsafe_code)]
+
+use {
+    cpio_reader::capi::{
+        cpio_entry_data, cpio_entry_metadata, cpio_entry_name, cpio_iter_init, cpio_iter_next,
+        CpioEntry, CpioFormat, CpioIter, CpioMetadata,
+    },
+    std::{env, fs, mem::MaybeUninit, ptr, slice},
+};
+
+const HEADER: &str = "include/cpio_reader.h";
+
+#[test]
+fn header_is_up_to_date() {
+    let generated = cbindgen::Builder::new()
+        .with_crate(env!("CARGO_MANIFEST_DIR"))
+        .with_config(cbindgen::Config::from_file("cbindgen.toml").unwrap())
+        .generate()
+        .unwrap();
+
+    let mut header = Vec::new();
+    generated.write(&mut header);
+
+    if env::var_os("CPIO_READER_BLESS").is_some() {
+        fs::write(HEADER, &header).unwrap();
+    }
+
+    assert_eq!(
+        String::from_utf8(header).unwrap(),
+        fs::read_to_string(HEADER).unwrap(),
+        "run `CPIO_READER_BLESS=1 cargo test --test capi` to update {HEADER}"
+    );
+}
+
+/// Reads the entries of `cpio` through the C API.
+fn read(cpio: &[u8]) -> Vec<(Vec<u8>, Vec<u8>, CpioMetadata)> {
+    let mut iter = MaybeUninit::<CpioIter>::uninit();
+    let mut entry = MaybeUninit::<CpioEntry>::uninit();
+    let mut entries = Vec::new();
+
+    unsafe {
+        assert!(cpio_iter_init(iter.as_mut_ptr(), cpio.as_ptr(), cpio.len()));
+
+        while cpio_iter_next(iter.as_mut_ptr(), entry.as_mut_ptr()) {
+            let mut name_len = 0;
+            let name = cpio_entry_name(entry.as_ptr(), ptr::addr_of_mut!(name_len));
+            let mut data_len = 0;
+            let data = cpio_entry_data(entry.as_ptr(), ptr::addr_of_mut!(data_len));
+            let mut metadata = MaybeUninit::uninit();
+
+            assert!(cpio_entry_metadata(entry.as_ptr(), metadata.as_mut_ptr()));
+
+            entries.push((
+                slice::from_raw_parts(name, name_len).to_vec(),
+                slice::from_raw_parts(data, data_len).to_vec(),
+                metadata.assume_init(),
+            ));
+        }
+    }
+
+    entries
+}
+
+#[test]
+fn entries_are_read_through_the_c_api() {
+    for (cpio, format) in [
+        ("tests/bin.cpio", CpioFormat::OldBinaryLittle),
+        ("tests/odc.cpio", CpioFormat::PortableAscii),
+        ("tests/newc.cpio", CpioFormat::NewAscii),
+        ("tests/crc.cpio", CpioFormat::NewCrc),
+    ] {
+        let cpio = fs::read(cpio).unwrap();
+        let expected = cpio_reader::iter_files(&cpio).collect::<Vec<_>>();
+        let entries = read(&cpio);
+
+        assert_eq!(entries.len(), expected.len());
+
+        for ((name, data, metadata), expected) in entries.iter().zip(expected) {
+            assert_eq!(name, expected.name().as_bytes());
+            assert_eq!(data, expected.file());
+            assert_eq!(metadata.format, format);
+            assert_eq!(metadata.mode, expected.raw_mode());
+            assert_eq!(metadata.uid, expected.uid());
+            assert_eq!(metadata.mtime, expected.mtime());
+            assert_eq!(metadata.rdev_major, expected.rdevice().major);
+            assert_eq!(metadata.has_check, expected.check().is_some());
+        }
+    }
+}
+
+#[test]
+fn null_pointers_and_corrupt_input_are_rejected() {
+    let mut iter = MaybeUninit::<CpioIter>::uninit();
+    let mut entry = MaybeUninit::<CpioEntry>::uninit();
+
+    unsafe {
+        assert!(!cpio_iter_init(ptr::null_mut(), b"".as_ptr(), 0));
+        assert!(!cpio_iter_init(iter.as_mut_ptr(), ptr::null(), 1));
+
+        assert!(cpio_iter_init(iter.as_mut_ptr(), ptr::null(), 0));
+        assert!(!cpio_iter_next(iter.as_mut_ptr(), entry.as_mut_ptr()));
+
+        let corrupt = b"070701 corrupt";
+        assert!(cpio_iter_init(
+            iter.as_mut_ptr(),
+            corrupt.as_ptr(),
+            corrupt.len()
+        ));
+        assert!(!cpio_iter_next(iter.as_mut_ptr(), entry.as_mut_ptr()));
+        assert!(!cpio_iter_next(iter.as_mut_ptr(), ptr::null_mut()));
+
+        assert!(cpio_entry_name(ptr::null(), ptr::null_mut()).is_null());
+        assert!(cpio_entry_data(ptr::null(), ptr::null_mut()).is_null());
+        assert!(!cpio_entry_metadata(ptr::null(), ptr::null_mut()));
+    }
+
+    assert!(read(&[]).is_empty());
+}
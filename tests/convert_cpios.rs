@@ -0,0 +1,271 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{
+        ConvertError, Converter, DeviceEncoding, DeviceNumber, Endianness, Entry, EntryBuilder,
+        Format, HardLinks, Mode,
+    },
+    std::fs,
+};
+
+const CPIOS: [&str; 4] = [
+    "tests/bin.cpio",
+    "tests/odc.cpio",
+    "tests/newc.cpio",
+    "tests/crc.cpio",
+];
+
+const FORMATS: [Format; 5] = [
+    Format::OldBinary(Endianness::Little),
+    Format::OldBinary(Endianness::Big),
+    Format::PortableAscii,
+    Format::NewAscii,
+    Format::NewCrc,
+];
+
+#[test]
+fn convert_between_all_formats() {
+    for cpio in CPIOS {
+        let original = fs::read(cpio).unwrap();
+
+        for format in FORMATS {
+            let mut converted = Vec::new();
+            cpio_reader::convert(&original, format, &mut converted).unwrap();
+
+            assert_eq!(converted.len() % 512, 0);
+
+            let originals = cpio_reader::iter_files(&original).collect::<Vec<_>>();
+            let converteds = cpio_reader::iter_files(&converted).collect::<Vec<_>>();
+            let original_links = HardLinks::new(&original);
+            let converted_links = HardLinks::new(&converted);
+
+            assert_eq!(originals.len(), converteds.len(), "{cpio} to {format:?}");
+
+            for (original, converted) in originals.iter().zip(&converteds) {
+                assert_eq!(converted.format(), format);
+                assert_eq!(converted.name(), original.name());
+                assert_eq!(
+                    converted_links.content(converted),
+                    original_links.content(original)
+                );
+                assert_eq!(converted.mode(), original.mode());
+                assert_eq!(converted.uid(), original.uid());
+                assert_eq!(converted.gid(), original.gid());
+                assert_eq!(converted.ino(), original.ino());
+                assert_eq!(converted.nlink(), original.nlink());
+                assert_eq!(converted.mtime(), original.mtime());
+                assert_eq!(device_numbers(converted), device_numbers(original));
+            }
+        }
+    }
+}
+
+#[test]
+fn converting_into_the_same_format_is_byte_exact() {
+    for (cpio, format) in CPIOS.iter().zip([
+        Format::OldBinary(Endianness::Little),
+        Format::PortableAscii,
+        Format::NewAscii,
+        Format::NewCrc,
+    ]) {
+        let original = fs::read(cpio).unwrap();
+
+        let mut converted = Vec::new();
+        cpio_reader::convert(&original, format, &mut converted).unwrap();
+
+        assert_eq!(converted, original, "{cpio}");
+    }
+}
+
+#[test]
+fn dev_is_split_into_major_and_minor() {
+    let bin = fs::read("tests/bin.cpio").unwrap();
+
+    let mut newc = Vec::new();
+    cpio_reader::convert(&bin, Format::NewAscii, &mut newc).unwrap();
+
+    let entry = cpio_reader::iter_files(&newc).next().unwrap();
+    assert_eq!(entry.dev(), None);
+    assert_eq!((entry.devmajor(), entry.devminor()), (Some(8), Some(2)));
+
+    let mut newc = Vec::new();
+    Converter::new(Format::NewAscii)
+        .device_encoding(DeviceEncoding::Split { minor_bits: 4 })
+        .convert(&bin, &mut newc)
+        .unwrap();
+
+    let entry = cpio_reader::iter_files(&newc).next().unwrap();
+    assert_eq!((entry.devmajor(), entry.devminor()), (Some(128), Some(2)));
+}
+
+#[test]
+fn device_encodings() {
+    assert_eq!(DeviceEncoding::Linux.decode(0x0802), (8, 2));
+    assert_eq!(DeviceEncoding::Linux.encode(8, 2), Some(0x0802));
+    assert_eq!(
+        DeviceEncoding::Linux.encode(259, 0x12345),
+        Some(0x1231_0345)
+    );
+    assert_eq!(DeviceEncoding::Linux.decode(0x1231_0345), (259, 0x12345));
+    assert_eq!(DeviceEncoding::Linux.encode(0x1000, 0), None);
+
+    let split = DeviceEncoding::Split { minor_bits: 8 };
+    assert_eq!(split.encode(0xff, 0xff), Some(0xffff));
+    assert_eq!(split.encode(0, 0x100), None);
+    assert_eq!(split.encode(0x100_0000, 0), None);
+    assert_eq!(split.decode(0x1234), (0x12, 0x34));
+}
+
+#[test]
+fn unrepresentable_entries_are_reported() {
+    let mut newc = Vec::new();
+
+    for (name, ino) in [("small", 1), ("large", 0x10000)] {
+        EntryBuilder::new(name, Format::NewAscii)
+            .ino(ino)
+            .build()
+            .write_to(&mut newc)
+            .unwrap();
+    }
+
+    cpio_reader::write_trailer(Format::NewAscii, &mut newc).unwrap();
+
+    let bin = Format::OldBinary(Endianness::Little);
+
+    match cpio_reader::convert(&newc, bin, &mut Vec::new()) {
+        Err(ConvertError::Unrepresentable {
+            name,
+            offset,
+            field,
+        }) => {
+            assert_eq!((name, offset, field), ("large", 116, "ino"));
+        }
+        e => panic!("unexpected result: {:?}", e),
+    }
+
+    let mut skipped = Vec::new();
+    let mut converted = Vec::new();
+    Converter::new(bin)
+        .convert_skipping(&newc, &mut converted, |entry, field| {
+            skipped.push((entry.name(), field));
+        })
+        .unwrap();
+
+    assert_eq!(skipped, [("large", "ino")]);
+    assert_eq!(
+        cpio_reader::iter_files(&converted)
+            .map(|entry| entry.name())
+            .collect::<Vec<_>>(),
+        ["small"]
+    );
+}
+
+#[test]
+fn corrupt_input_is_reported() {
+    let mut newc = fs::read("tests/newc.cpio").unwrap();
+    newc.truncate(300);
+
+    assert!(matches!(
+        cpio_reader::convert(&newc, Format::PortableAscii, &mut Vec::new()),
+        Err(ConvertError::Corrupt { offset: 256 })
+    ));
+}
+
+fn device_numbers(entry: &Entry<'_>) -> [(u32, u32); 2] {
+    let split = |dev: Option<u32>, major: Option<u32>, minor: Option<u32>| match dev {
+        Some(dev) => DeviceEncoding::Linux.decode(dev),
+        None => (major.unwrap(), minor.unwrap()),
+    };
+
+    [
+        split(entry.dev(), entry.devmajor(), entry.devminor()),
+        split(entry.rdev(), entry.rdevmajor(), entry.rdevminor()),
+    ]
+}
+
+#[test]
+fn device_numbers_are_format_agnostic() {
+    for format in [
+        Format::OldBinary(Endianness::Little),
+        Format::PortableAscii,
+        Format::NewAscii,
+        Format::NewCrc,
+    ] {
+        let builder = EntryBuilder::new("dev/sda2", format).mode(Mode::BLOCK_SPECIAL_DEVICE);
+        let builder = match format {
+            Format::OldBinary(_) | Format::PortableAscii => builder.dev(0x0803).rdev(0x0802),
+            Format::NewAscii | Format::NewCrc => {
+                builder.devmajor(8).devminor(3).rdevmajor(8).rdevminor(2)
+            }
+        };
+        let entry = builder.build();
+
+        assert_eq!(entry.device(), DeviceNumber::new(8, 3), "{format:?}");
+        assert_eq!(entry.rdevice(), DeviceNumber::new(8, 2), "{format:?}");
+    }
+
+    let entry = EntryBuilder::new("dev/sda2", Format::PortableAscii)
+        .rdev(0x0802)
+        .build();
+    let split = DeviceEncoding::Split { minor_bits: 4 };
+
+    assert_eq!(entry.rdevice_with(split), DeviceNumber::new(0x80, 2));
+    assert_eq!(entry.rdevice().to_string(), "8:2");
+    assert_eq!(<(u32, u32)>::from(entry.rdevice()), (8, 2));
+}
+
+#[test]
+fn formats_are_named_as_gnu_cpio_options() {
+    let formats = [
+        (Format::OldBinary(Endianness::Little), "bin"),
+        (Format::OldBinary(Endianness::Big), "bin-be"),
+        (Format::PortableAscii, "odc"),
+        (Format::NewAscii, "newc"),
+        (Format::NewCrc, "crc"),
+    ];
+
+    for (format, name) in formats {
+        assert_eq!(format.to_string(), name);
+        assert_eq!(name.parse(), Ok(format));
+    }
+
+    assert_eq!("tar".parse::<Format>(), Err(cpio_reader::ParseFormatError));
+}
+
+#[test]
+fn hard_link_contents_are_moved_to_where_the_format_stores_them() {
+    let newc = fs::read("tests/newc.cpio").unwrap();
+
+    for format in FORMATS {
+        let mut converted = Vec::new();
+        cpio_reader::convert(&newc, format, &mut converted).unwrap();
+
+        let sizes = cpio_reader::iter_files(&converted)
+            .filter(|entry| entry.name().ends_with("derich") && entry.symlink_target().is_none())
+            .map(|entry| (entry.name().to_owned(), entry.file().len()))
+            .collect::<Vec<_>>();
+
+        let expected = match format {
+            Format::OldBinary(_) | Format::PortableAscii => [5, 5],
+            Format::NewAscii | Format::NewCrc => [0, 5],
+        };
+
+        assert_eq!(
+            sizes,
+            [
+                ("skills/derich".to_owned(), expected[0]),
+                ("magics/derich".to_owned(), expected[1])
+            ],
+            "{format:?}"
+        );
+
+        for entry in cpio_reader::iter_files(&converted) {
+            assert_eq!(
+                entry.check(),
+                format
+                    .eq(&Format::NewCrc)
+                    .then(|| entry.computed_checksum())
+            );
+        }
+    }
+}
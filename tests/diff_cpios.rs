@@ -0,0 +1,93 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{Changes, Difference, EntryBuilder, Format, Mode},
+    std::fs,
+};
+
+fn archive(format: Format, entries: &[(&str, Mode, u32, &[u8])]) -> Vec<u8> {
+    let mut cpio = Vec::new();
+
+    for (name, mode, uid, file) in entries {
+        EntryBuilder::new(name, format)
+            .mode(*mode)
+            .uid(*uid)
+            .file(file)
+            .build()
+            .write_to(&mut cpio)
+            .unwrap();
+    }
+
+    cpio_reader::write_trailer(format, &mut cpio).unwrap();
+
+    cpio
+}
+
+#[test]
+fn added_removed_and_changed_entries_are_reported() {
+    let file = Mode::REGULAR_FILE | Mode::USER_READABLE;
+
+    let old = archive(
+        Format::NewAscii,
+        &[
+            ("./etc/fstab", file, 0, b"/dev/sda1 / ext4"),
+            ("./etc/hostname", file, 0, b"old"),
+            ("./etc/passwd", file, 0, b"root"),
+            ("./init", file, 0, b"#!/bin/sh"),
+        ],
+    );
+    let new = archive(
+        Format::PortableAscii,
+        &[
+            ("etc/passwd", file | Mode::USER_WRITABLE, 1000, b"root"),
+            ("etc/hostname", file, 0, b"new"),
+            ("etc/fstab", file, 0, b"/dev/sda1 / ext4"),
+            ("etc/shadow", file, 0, b"root:*"),
+        ],
+    );
+
+    let differences = cpio_reader::diff(&old, &new);
+    let displayed = differences
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        displayed,
+        [
+            "~ etc/hostname: content",
+            "~ etc/passwd: mode, uid",
+            "+ etc/shadow",
+            "- init",
+        ]
+    );
+
+    let Difference::Changed { changes, .. } = differences[1] else {
+        panic!("{:?}", differences[1]);
+    };
+
+    assert_eq!(changes, Changes::MODE | Changes::UID);
+    assert!(changes.is_metadata_only());
+    assert!(!Changes::CONTENT.is_metadata_only());
+    assert!(!Changes::empty().is_metadata_only());
+}
+
+#[test]
+fn same_trees_in_different_formats_have_no_content_changes() {
+    let bin = fs::read("tests/bin.cpio").unwrap();
+
+    for cpio in ["tests/odc.cpio", "tests/newc.cpio", "tests/crc.cpio"] {
+        let other = fs::read(cpio).unwrap();
+
+        for difference in cpio_reader::diff(&bin, &other) {
+            match difference {
+                Difference::Changed { changes, .. } => {
+                    assert!(changes.is_metadata_only(), "{}: {}", cpio, difference);
+                }
+                _ => panic!("{}: {}", cpio, difference),
+            }
+        }
+    }
+
+    assert!(cpio_reader::diff(&bin, &bin).is_empty());
+}
@@ -0,0 +1,151 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{ArchiveEditor, DeviceNumber, EntryBuilder, Format, Mode},
+    std::fs,
+};
+
+const CPIOS: [&str; 4] = [
+    "tests/bin.cpio",
+    "tests/odc.cpio",
+    "tests/newc.cpio",
+    "tests/crc.cpio",
+];
+
+#[test]
+fn untouched_archives_are_written_byte_identically() {
+    for cpio in CPIOS {
+        let original = fs::read(cpio).unwrap();
+
+        let mut written = Vec::new();
+        ArchiveEditor::new(&original)
+            .unwrap()
+            .write_to(&mut written)
+            .unwrap();
+
+        assert_eq!(written, original, "{cpio}");
+    }
+}
+
+#[test]
+fn remove_replace_and_append() {
+    for cpio in CPIOS {
+        let original = fs::read(cpio).unwrap();
+        let mut editor = ArchiveEditor::new(&original).unwrap();
+
+        let format = editor.format();
+
+        assert!(editor.remove("derich").is_some());
+        assert!(editor.remove("derich").is_none());
+        assert!(editor.replace_file("magics/derich", b"Queen\n").is_some());
+        editor.append(
+            EntryBuilder::new("magics/init", Format::NewAscii)
+                .mode(Mode::REGULAR_FILE | Mode::from_bits_truncate(0o755))
+                .uid(1000)
+                .gid(1000)
+                .mtime(1_629_615_560)
+                .devmajor(0)
+                .devminor(26)
+                .file(b"#!/bin/sh\n")
+                .build(),
+        );
+        editor.append(
+            EntryBuilder::new("dev/console", Format::NewAscii)
+                .mode(Mode::CHARACTER_SPECIAL_DEVICE | Mode::from_bits_truncate(0o600))
+                .rdevmajor(5)
+                .rdevminor(1)
+                .build(),
+        );
+
+        let mut written = Vec::new();
+        editor.write_to(&mut written).unwrap();
+
+        assert_eq!(written.len() % 512, 0, "{cpio}");
+
+        let entries = cpio_reader::iter_files(&written).collect::<Vec<_>>();
+
+        assert_eq!(entries.len(), 7, "{cpio}");
+        assert!(entries.iter().all(|entry| entry.format() == format));
+        assert!(entries.iter().all(|entry| entry.name() != "derich"));
+
+        let derich = entries
+            .iter()
+            .find(|entry| entry.name() == "magics/derich")
+            .unwrap();
+        assert_eq!(derich.file(), b"Queen\n", "{cpio}");
+        assert_eq!(derich.uid(), 1000);
+
+        let init = &entries[5];
+        assert_eq!(init.name(), "magics/init");
+        assert_eq!(init.file(), b"#!/bin/sh\n");
+        assert_eq!(
+            init.mode(),
+            Mode::REGULAR_FILE | Mode::from_bits_truncate(0o755)
+        );
+        assert_eq!(init.mtime(), 1_629_615_560);
+        assert_eq!(init.device(), DeviceNumber::new(0, 26), "{cpio}");
+
+        // The device numbers are converted into the format of the edited cpio file.
+        let console = entries.last().unwrap();
+        assert_eq!(console.name(), "dev/console");
+        assert_eq!(console.rdevice(), DeviceNumber::new(5, 1), "{cpio}");
+
+        // The untouched entries are copied as they are.
+        let skills = cpio_reader::iter_files(&original)
+            .find(|entry| entry.name() == "skills")
+            .unwrap();
+        let written_skills = entries
+            .iter()
+            .find(|entry| entry.name() == "skills")
+            .unwrap();
+        assert_eq!(
+            &written[written_skills.header_offset()..][..written_skills.entry_len()],
+            &original[skills.header_offset()..][..skills.entry_len()],
+        );
+    }
+}
+
+#[test]
+fn removing_the_last_link_moves_the_content() {
+    let newc = fs::read("tests/newc.cpio").unwrap();
+    let mut editor = ArchiveEditor::new(&newc).unwrap();
+
+    let removed = editor.remove("magics/derich").unwrap();
+    assert_eq!(removed.file().len(), 5);
+    assert_eq!(editor.get("skills/derich").unwrap().file(), removed.file());
+
+    let mut written = Vec::new();
+    editor.write_to(&mut written).unwrap();
+
+    let derich = cpio_reader::iter_files(&written)
+        .find(|entry| entry.name() == "skills/derich")
+        .unwrap();
+    assert_eq!(derich.file(), removed.file());
+}
+
+#[test]
+fn reject_corrupt_archives() {
+    let mut newc = fs::read("tests/newc.cpio").unwrap();
+
+    assert!(ArchiveEditor::new(&[]).is_none());
+
+    newc.truncate(200);
+    assert!(ArchiveEditor::new(&newc).is_none());
+}
+
+#[test]
+fn unrepresentable_fields_are_reported() {
+    let bin = fs::read("tests/bin.cpio").unwrap();
+    let mut editor = ArchiveEditor::new(&bin).unwrap();
+
+    editor.append(
+        EntryBuilder::new("large_inode", Format::NewAscii)
+            .ino(0x10000)
+            .build(),
+    );
+
+    assert_eq!(
+        editor.write_to(&mut Vec::new()),
+        Err(cpio_reader::WriteError::Unrepresentable("ino"))
+    );
+}
@@ -0,0 +1,94 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{ArchiveEditor, Component, EntryPath},
+    std::{fs, path::Path},
+};
+
+#[test]
+fn paths_are_compared_component_wise() {
+    let paths = [
+        "usr/bin/sh",
+        "./usr/bin/sh",
+        "/usr/bin/sh",
+        "//usr//bin/./sh/",
+        "././usr/bin/sh",
+    ];
+
+    for path in paths {
+        let path = EntryPath::new(path);
+
+        assert_eq!(path, EntryPath::new("usr/bin/sh"));
+        assert_eq!(path.to_string(), "usr/bin/sh");
+        assert_eq!(path.depth(), 3);
+        assert_eq!(path.file_name(), Some("sh"));
+        assert_eq!(path.parent(), Some(EntryPath::new("usr/bin")));
+        assert!(path.starts_with(EntryPath::new("./usr")));
+        assert!(!path.starts_with(EntryPath::new("us")));
+        assert!(!path.escapes());
+    }
+
+    assert_ne!(EntryPath::new("usr/bin"), EntryPath::new("usr/bin/sh"));
+    assert_ne!(EntryPath::new("usr/../bin"), EntryPath::new("bin"));
+}
+
+#[test]
+fn trim_leading() {
+    assert_eq!(EntryPath::new("./usr/bin/sh").trim_leading(), "usr/bin/sh");
+    assert_eq!(EntryPath::new("//usr/bin/sh").trim_leading(), "usr/bin/sh");
+    assert_eq!(EntryPath::new("/./usr//bin").trim_leading(), "usr//bin");
+    assert_eq!(EntryPath::new(".").trim_leading(), "");
+    assert_eq!(EntryPath::new("..").trim_leading(), "..");
+}
+
+#[test]
+fn components() {
+    let components = EntryPath::new("./a//../b/.")
+        .components()
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        components,
+        [
+            Component::Normal("a"),
+            Component::ParentDir,
+            Component::Normal("b")
+        ]
+    );
+
+    assert_eq!(EntryPath::new(".").components().count(), 0);
+    assert_eq!(EntryPath::new(".").parent(), None);
+    assert_eq!(EntryPath::new("a").parent(), Some(EntryPath::new("")));
+    assert_eq!(EntryPath::new("a/..").file_name(), None);
+}
+
+#[test]
+fn escapes_and_join_to() {
+    let root = Path::new("/tmp/root");
+
+    for path in ["../etc/passwd", "usr/../../etc/passwd", "/.."] {
+        assert!(EntryPath::new(path).escapes(), "{}", path);
+        assert_eq!(EntryPath::new(path).join_to(root), None, "{path}");
+    }
+
+    assert_eq!(
+        EntryPath::new("/usr/bin/sh").join_to(root),
+        Some(root.join("usr/bin/sh"))
+    );
+    assert_eq!(
+        EntryPath::new("./usr/lib/../bin/sh").join_to(root),
+        Some(root.join("usr/bin/sh"))
+    );
+    assert_eq!(EntryPath::new(".").join_to(root), Some(root.to_path_buf()));
+}
+
+#[test]
+fn editor_looks_up_entries_by_path() {
+    let cpio = fs::read("tests/newc.cpio").unwrap();
+    let editor = ArchiveEditor::new(&cpio).unwrap();
+
+    let entry = editor.get("./magics//derich").unwrap();
+
+    assert_eq!(entry.name(), "magics/derich");
+    assert_eq!(entry.path(), EntryPath::new("/magics/derich"));
+}
@@ -0,0 +1,86 @@
+#![allow(missing_docs)]
+
+//! Compares the parsed entries with the listings recorded by GNU cpio in `tests/gnu_cpio/`.
+
+use {
+    cpio_reader::{Entry, Mode, Timestamp},
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+#[test]
+fn entries_match_gnu_cpio_listings() {
+    let recorded_at = fs::read_to_string("tests/gnu_cpio/recorded_at")
+        .expect("the corpus is not recorded; run `tests/gnu_cpio/record.sh`");
+    let recorded_at = Timestamp::from_secs(recorded_at.trim().parse().unwrap());
+
+    let mut compared = 0;
+
+    for listing in fs::read_dir("tests/gnu_cpio").unwrap() {
+        let listing = listing.unwrap().path();
+
+        if listing.extension() != Some("list".as_ref()) {
+            continue;
+        }
+
+        let cpio = cpio_path(&listing);
+        let cpio = fs::read(&cpio).unwrap_or_else(|e| panic!("{}: {}", cpio.display(), e));
+
+        let expected = fs::read_to_string(&listing).unwrap();
+        let actual = cpio_reader::iter_files(&cpio)
+            .map(|entry| long_format(&entry, recorded_at) + "\n")
+            .collect::<String>();
+
+        assert_eq!(actual, expected, "{}", listing.display());
+
+        compared += 1;
+    }
+
+    assert_ne!(compared, 0);
+}
+
+/// Returns the path to the cpio file listed in `listing`.
+fn cpio_path(listing: &Path) -> PathBuf {
+    let in_corpus = listing.with_extension("cpio");
+
+    if in_corpus.exists() {
+        in_corpus
+    } else {
+        Path::new("tests").join(in_corpus.file_name().unwrap())
+    }
+}
+
+/// Formats `entry` as `long_format` in `copyin.c` of GNU cpio with `--numeric-uid-gid`, which
+/// ran at `now`.
+fn long_format(entry: &Entry<'_>, now: Timestamp) -> String {
+    let file_type = entry.mode().file_type();
+
+    let size =
+        if file_type == Mode::CHARACTER_SPECIAL_DEVICE || file_type == Mode::BLOCK_SPECIAL_DEVICE {
+            let rdevice = entry.rdevice();
+
+            format!("{:3}, {:3}", rdevice.major, rdevice.minor)
+        } else {
+            format!("{:8}", entry.file().len())
+        };
+
+    let mut line = format!(
+        "{} {:3} {:<8} {:<8} {} {} {}",
+        entry.mode(),
+        entry.nlink(),
+        entry.uid(),
+        entry.gid(),
+        size,
+        entry.modified().display_ls(now),
+        entry.name()
+    );
+
+    if let Some(target) = entry.symlink_target() {
+        line += " -> ";
+        line += target;
+    }
+
+    line
+}
@@ -0,0 +1,58 @@
+#!/bin/sh
+# Records cpio files and their listings with GNU cpio for the differential test in
+# `tests/gnu_cpio.rs`.
+#
+# Run this script from any directory. It overwrites the `.cpio` and `.list` files and `recorded_at`
+# in the directory containing it.
+
+set -eu
+
+cd "$(dirname "$0")"
+
+if ! cpio --version 2>/dev/null | grep -q 'GNU cpio'; then
+    echo 'GNU cpio is required.' >&2
+    exit 1
+fi
+
+# The listings show the time of day only for the times within six months before the recording.
+date -u +%s >recorded_at
+
+list() {
+    TZ=UTC LC_ALL=C cpio --quiet -itvn <"$1"
+}
+
+tree=$(mktemp -d)
+trap 'rm -rf "$tree"' EXIT
+
+(
+    cd "$tree"
+
+    mkdir -p dir/sub
+    printf 'hello\n' >dir/file
+    : >empty
+    ln dir/file dir/hardlink
+    ln -s dir/file symlink
+    ln -s "$(printf '%0200d' 0)" long_symlink
+    printf 'long\n' >"$(printf '%0200d' 0)"
+    head -c 70000 /dev/zero | tr '\0' a >dir/sub/large
+    mkfifo fifo
+    chmod 4755 dir/sub/large
+    chmod 1777 dir/sub
+
+    if [ "$(id -u)" = 0 ]; then
+        mknod null c 1 3
+        mknod loop b 7 0
+    fi
+
+    find . -exec touch -h -d '2021-08-22 12:34:56 UTC' {} +
+)
+
+for format in bin odc newc crc; do
+    (cd "$tree" && find . | sort | cpio --quiet -o -H "$format") >"gnu-$format.cpio"
+    list "gnu-$format.cpio" >"gnu-$format.list"
+done
+
+# The fixtures in `tests/`.
+for format in bin odc newc crc; do
+    list "../$format.cpio" >"$format.list"
+done
@@ -0,0 +1,93 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{LimitError, ParseLimits},
+    std::fs,
+};
+
+/// Returns the names of the entries read within `limits`, and the error if any.
+fn read(limits: ParseLimits) -> (Vec<String>, Option<LimitError>) {
+    let cpio = fs::read("tests/newc.cpio").unwrap();
+
+    let mut names = Vec::new();
+
+    for entry in limits.iter_files(&cpio) {
+        match entry {
+            Ok(entry) => names.push(entry.name().to_owned()),
+            Err(e) => return (names, Some(e)),
+        }
+    }
+
+    (names, None)
+}
+
+#[test]
+fn no_limits_by_default() {
+    let cpio = fs::read("tests/newc.cpio").unwrap();
+
+    let limited = ParseLimits::new()
+        .iter_files(&cpio)
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+
+    assert_eq!(limited, cpio_reader::iter_files(&cpio).collect::<Vec<_>>());
+}
+
+#[test]
+fn limits_within_the_archive_are_accepted() {
+    let limits = ParseLimits::new()
+        .max_entries(6)
+        .max_name_len(15)
+        .max_file_size(13)
+        .max_total_size(31)
+        .max_path_depth(2);
+
+    let (names, error) = read(limits);
+
+    assert_eq!(names.len(), 6);
+    assert_eq!(error, None);
+}
+
+#[test]
+fn each_limit_stops_the_iteration() {
+    let cpio = fs::read("tests/newc.cpio").unwrap();
+    let offsets = cpio_reader::iter_files(&cpio)
+        .map(|entry| entry.header_offset())
+        .collect::<Vec<_>>();
+
+    let cases = [
+        (
+            ParseLimits::new().max_entries(2),
+            2,
+            LimitError::TooManyEntries { offset: offsets[2] },
+        ),
+        (
+            ParseLimits::new().max_name_len(6),
+            3,
+            LimitError::NameTooLong { offset: offsets[3] },
+        ),
+        (
+            ParseLimits::new().max_file_size(12),
+            0,
+            LimitError::FileTooLarge { offset: offsets[0] },
+        ),
+        (
+            ParseLimits::new().max_total_size(20),
+            5,
+            LimitError::TotalSizeTooLarge { offset: offsets[5] },
+        ),
+        (
+            ParseLimits::new().max_path_depth(1),
+            3,
+            LimitError::PathTooDeep { offset: offsets[3] },
+        ),
+    ];
+
+    for (limits, read_entries, error) in cases {
+        let (names, e) = read(limits);
+
+        assert_eq!(names.len(), read_entries, "{error:?}");
+        assert_eq!(e, Some(error));
+        assert_eq!(e.map(|e| e.offset()), Some(offsets[read_entries]));
+    }
+}
@@ -0,0 +1,105 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{ArchiveEditor, Format, RpmPayload},
+    std::fs,
+};
+
+const CPIOS: [&str; 4] = [
+    "tests/bin.cpio",
+    "tests/odc.cpio",
+    "tests/newc.cpio",
+    "tests/crc.cpio",
+];
+
+const RPMS: [&str; 3] = ["tests/gzip.rpm", "tests/xz.rpm", "tests/stripped.rpm"];
+
+/// Runs every reader of this crate on `bin`. None of them may panic.
+fn read_everything(bin: &[u8]) {
+    for entry in cpio_reader::iter_files(bin) {
+        let _ = (
+            entry.name(),
+            entry.file(),
+            entry.size(),
+            entry.computed_checksum(),
+        );
+    }
+
+    let mut copy = bin.to_vec();
+
+    for mut entry in cpio_reader::iter_files_mut(&mut copy) {
+        entry.file_mut().reverse();
+        entry.update_check();
+    }
+
+    if let Some(editor) = ArchiveEditor::new(bin) {
+        let mut edited = Vec::new();
+        let _ = editor.write_to(&mut edited);
+    }
+
+    let _ = cpio_reader::convert(bin, Format::NewCrc, &mut Vec::new());
+
+    let _ = RpmPayload::new(bin).map(|payload| payload.iter_files().count());
+}
+
+#[test]
+fn truncated_files_do_not_panic() {
+    for path in CPIOS.iter().chain(&RPMS) {
+        let bin = fs::read(path).unwrap();
+
+        for len in 0..bin.len() {
+            read_everything(&bin[..len]);
+        }
+    }
+}
+
+#[test]
+fn corrupt_bytes_do_not_panic() {
+    for path in CPIOS.iter().chain(&RPMS) {
+        let bin = fs::read(path).unwrap();
+
+        for i in 0..bin.len() {
+            for byte in [0x00, 0x01, b'0', b'7', b'F', b'X', 0x7f, 0xff] {
+                let mut corrupt = bin.clone();
+                corrupt[i] = byte;
+
+                read_everything(&corrupt);
+            }
+        }
+    }
+}
+
+#[test]
+fn missing_padding_does_not_panic() {
+    // The headers are followed by names whose padding, and the contents, are missing.
+    let newc = format!(
+        "070701{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}ab\0",
+        1, 0o100_644, 0, 0, 1, 0, 0, 0, 0, 0, 0, 3, 0
+    );
+    let crc = newc.replacen("070701", "070702", 1);
+    let bin = [
+        0xc7, 0x71, 0, 0, 1, 0, 0xa4, 0x81, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0,
+        b'a', 0,
+    ];
+
+    for cpio in [newc.as_bytes(), crc.as_bytes(), &bin] {
+        read_everything(cpio);
+    }
+}
+
+#[test]
+fn zero_sized_names_are_rejected() {
+    let newc = format!(
+        "070701{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}",
+        1, 0o100_644, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0
+    );
+    let odc = format!(
+        "070707{:06o}{:06o}{:06o}{:06o}{:06o}{:06o}{:06o}{:011o}{:06o}{:011o}",
+        0, 1, 0o100_644, 0, 0, 1, 0, 0, 0, 0
+    );
+
+    for cpio in [newc, odc] {
+        assert_eq!(cpio_reader::iter_files(cpio.as_bytes()).count(), 0);
+        read_everything(cpio.as_bytes());
+    }
+}
@@ -0,0 +1,158 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{EntryBuilder, Format, Manifest, Mode},
+    sha2::{Digest, Sha256},
+    std::{fmt::Write, fs},
+};
+
+/// The SHA-256 digest of `abc`.
+const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+/// The SHA-256 digest of the empty string.
+const EMPTY: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+fn archive() -> Vec<u8> {
+    let mut cpio = Vec::new();
+    let file = Mode::REGULAR_FILE | Mode::from_bits(0o644).unwrap();
+
+    let entries: [(&str, Mode, &[u8]); 4] = [
+        (
+            "./etc",
+            Mode::DIRECTORY | Mode::from_bits(0o755).unwrap(),
+            b"",
+        ),
+        ("./etc/abc", file, b"abc"),
+        ("./etc/empty", file, b""),
+        ("./etc/new\nline", file, b"abc"),
+    ];
+
+    for (name, mode, content) in entries {
+        EntryBuilder::new(name, Format::NewAscii)
+            .mode(mode)
+            .uid(1000)
+            .gid(100)
+            .file(content)
+            .build()
+            .write_to(&mut cpio)
+            .unwrap();
+    }
+
+    cpio_reader::write_trailer(Format::NewAscii, &mut cpio).unwrap();
+
+    cpio
+}
+
+#[test]
+fn digest() {
+    let cpio = archive();
+    let entry = cpio_reader::iter_files(&cpio).nth(1).unwrap();
+
+    assert_eq!(entry.digest::<Sha256>(), Sha256::digest(b"abc"));
+}
+
+#[test]
+fn manifest_lists_regular_files_with_metadata() {
+    let mut manifest = String::new();
+
+    Manifest::new()
+        .metadata(true)
+        .write::<Sha256, _>(&archive(), &mut manifest)
+        .unwrap();
+
+    assert_eq!(
+        manifest,
+        format!(
+            "{ABC}  100644  1000:100  etc/abc\n\
+             {EMPTY}  100644  1000:100  etc/empty\n\
+             \\{ABC}  100644  1000:100  etc/new\\nline\n"
+        )
+    );
+}
+
+#[test]
+fn manifest_is_sha256sum_compatible_by_default() {
+    let cpio = archive();
+    let mut manifest = String::new();
+
+    Manifest::new()
+        .write::<Sha256, _>(&cpio, &mut manifest)
+        .unwrap();
+
+    let files = cpio_reader::iter_files(&cpio)
+        .filter(|entry| entry.mode().file_type() == Mode::REGULAR_FILE)
+        .collect::<Vec<_>>();
+
+    assert_eq!(manifest.lines().count(), files.len());
+
+    for (line, entry) in manifest.lines().zip(files) {
+        let (digest, path) = parse_sha256sum_line(line);
+
+        assert_eq!(digest, hex(&entry.digest::<Sha256>()));
+        assert_eq!(path, entry.path().to_string());
+    }
+
+    assert_eq!(
+        manifest,
+        format!("{ABC}  etc/abc\n{EMPTY}  etc/empty\n\\{ABC}  etc/new\\nline\n")
+    );
+}
+
+/// Parses a line of the output of `sha256sum` into the digest and the unescaped path, as
+/// `sha256sum -c` does.
+fn parse_sha256sum_line(line: &str) -> (&str, String) {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+
+    let (digest, path) = line.split_at(64);
+    let path = path.strip_prefix("  ").unwrap();
+
+    if !escaped {
+        return (digest, path.to_owned());
+    }
+
+    let mut unescaped = String::new();
+    let mut chars = path.chars();
+
+    while let Some(c) = chars.next() {
+        unescaped.push(match c {
+            '\\' => match chars.next() {
+                Some('\\') => '\\',
+                Some('n') => '\n',
+                c => panic!("invalid escape: {:?}", c),
+            },
+            c => c,
+        });
+    }
+
+    (digest, unescaped)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        write!(hex, "{byte:02x}").unwrap();
+        hex
+    })
+}
+
+#[test]
+fn hard_links_are_hashed_with_the_shared_content() {
+    let cpio = fs::read("tests/newc.cpio").unwrap();
+
+    let mut manifest = String::new();
+
+    Manifest::new()
+        .write::<Sha256, _>(&cpio, &mut manifest)
+        .unwrap();
+
+    let digests = manifest
+        .lines()
+        .filter(|line| line.ends_with("/derich") || line.ends_with("  derich"))
+        .map(|line| line.split_once("  ").unwrap().0)
+        .collect::<Vec<_>>();
+
+    assert!(digests.len() > 1);
+    assert!(digests.iter().all(|digest| *digest == digests[0]));
+    assert_ne!(digests[0], EMPTY);
+}
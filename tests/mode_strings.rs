@@ -0,0 +1,97 @@
+#![allow(missing_docs)]
+
+use cpio_reader::{Mode, ParseModeError};
+
+fn mode(bits: u32) -> Mode {
+    Mode::from_bits(bits).unwrap()
+}
+
+const CASES: [(u32, &str); 9] = [
+    (0o040_755, "drwxr-xr-x"),
+    (0o120_777, "lrwxrwxrwx"),
+    (0o020_660, "crw-rw----"),
+    (0o060_640, "brw-r-----"),
+    (0o100_644, "-rw-r--r--"),
+    (0o010_600, "prw-------"),
+    (0o140_755, "srwxr-xr-x"),
+    (0o104_755, "-rwsr-xr-x"),
+    (0o041_777, "drwxrwxrwt"),
+];
+
+#[test]
+fn modes_are_displayed_symbolically() {
+    for (bits, symbolic) in CASES {
+        assert_eq!(mode(bits).to_string(), symbolic);
+    }
+
+    assert_eq!(mode(0o106_644).to_string(), "-rwSr-Sr--");
+    assert_eq!(mode(0o101_644).to_string(), "-rw-r--r-T");
+    assert_eq!(mode(0o755).to_string(), "?rwxr-xr-x");
+}
+
+#[test]
+fn symbolic_modes_are_parsed() {
+    for (bits, symbolic) in CASES {
+        assert_eq!(symbolic.parse(), Ok(mode(bits)), "{symbolic}");
+    }
+
+    assert_eq!("-rwSr-Sr-T".parse(), Ok(mode(0o107_644)));
+    assert_eq!("rwxr-xr-x".parse(), Ok(mode(0o755)));
+    assert_eq!("?rwxr-xr-x".parse(), Ok(mode(0o755)));
+}
+
+#[test]
+fn displayed_modes_are_parsed_back() {
+    let modes = CASES
+        .iter()
+        .map(|(bits, _)| *bits)
+        .chain([0o106_644, 0o101_644, 0o107_644, 0o755, 0]);
+
+    for bits in modes {
+        assert_eq!(mode(bits).to_string().parse(), Ok(mode(bits)), "{bits:o}");
+    }
+}
+
+#[test]
+fn octal_modes_are_parsed() {
+    assert_eq!("0755".parse(), Ok(mode(0o755)));
+    assert_eq!("755".parse(), Ok(mode(0o755)));
+    assert_eq!("0o4755".parse(), Ok(mode(0o4755)));
+    assert_eq!("100644".parse(), Ok(mode(0o100_644)));
+    assert_eq!("0160000".parse(), Ok(Mode::from_bits_retain(0o160_000)));
+    assert_eq!("0o177777".parse(), Ok(Mode::from_bits_retain(0o177_777)));
+}
+
+#[test]
+fn invalid_modes_are_rejected() {
+    for invalid in [
+        "",
+        "0o",
+        "0789",
+        "0200000",
+        "0o37777777777",
+        "-0755",
+        "drwxr-xr-",
+        "xrwxr-xr-x",
+        "drwxr-xr-xx",
+        "dr-xr-xr-s",
+        "dwrxr-xr-x",
+    ] {
+        assert_eq!(invalid.parse::<Mode>(), Err(ParseModeError), "{invalid}");
+    }
+}
+
+#[test]
+fn file_types_are_extracted() {
+    assert_eq!(mode(0o120_777).file_type(), Mode::SYMBOLIK_LINK);
+    assert_eq!(mode(0o100_644).file_type(), Mode::REGULAR_FILE);
+    assert_eq!(
+        Mode::from_bits_retain(0o160_644).file_type().bits(),
+        0o160_000
+    );
+    assert_ne!(
+        Mode::from_bits_retain(0o160_644).file_type(),
+        Mode::SYMBOLIK_LINK
+    );
+    assert!(mode(0o755).file_type().is_empty());
+}
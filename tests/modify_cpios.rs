@@ -0,0 +1,85 @@
+#![allow(missing_docs)]
+
+use std::fs;
+
+#[test]
+fn modify_newc_in_place() {
+    let mut newc = fs::read("tests/newc.cpio").unwrap();
+    let original_len = newc.len();
+
+    for mut entry in cpio_reader::iter_files_mut(&mut newc) {
+        if entry.name() == "magics/derich" {
+            entry.file_mut().copy_from_slice(b"Queen");
+        }
+    }
+
+    assert_eq!(newc.len(), original_len);
+    assert_eq!(cpio_reader::iter_files(&newc).count(), 6);
+    assert_eq!(
+        cpio_reader::iter_files(&newc)
+            .find(|entry| entry.name() == "magics/derich")
+            .map(|entry| entry.file()),
+        Some(&b"Queen"[..])
+    );
+}
+
+#[test]
+fn update_check_after_modifying_crc() {
+    let mut crc = fs::read("tests/crc.cpio").unwrap();
+
+    let mut corrupted = crc.clone();
+    replace_derich_with_queen(&mut corrupted, false);
+
+    assert!(cpio_reader::iter_files(&corrupted).all(|entry| entry.name() != "magics/derich"));
+
+    replace_derich_with_queen(&mut crc, true);
+
+    let entry = cpio_reader::iter_files(&crc)
+        .find(|entry| entry.name() == "magics/derich")
+        .unwrap();
+
+    assert_eq!(entry.file(), b"Queen");
+    assert_eq!(entry.check(), Some(entry.computed_checksum()));
+    assert_eq!(cpio_reader::iter_files(&crc).count(), 6);
+}
+
+#[test]
+fn update_check_does_nothing_for_non_crc() {
+    let mut odc = fs::read("tests/odc.cpio").unwrap();
+    let original = odc.clone();
+
+    assert!(cpio_reader::iter_files_mut(&mut odc).all(|mut entry| !entry.update_check()));
+    assert_eq!(odc, original);
+}
+
+fn replace_derich_with_queen(cpio: &mut [u8], update_check: bool) {
+    for mut entry in cpio_reader::iter_files_mut(cpio) {
+        if entry.name() == "magics/derich" {
+            entry.file_mut().copy_from_slice(b"Queen");
+
+            if update_check {
+                assert!(entry.update_check());
+            }
+
+            assert_eq!(entry.entry().file(), b"Queen");
+        }
+    }
+}
+
+#[test]
+fn missing_padding_at_the_end_is_accepted() {
+    // The name of the last entry is not followed by its padding, and the file is empty.
+    let mut newc = format!(
+        "070701{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}ab\0",
+        1, 0o100_644, 0, 0, 1, 0, 0, 0, 0, 0, 0, 3, 0
+    )
+    .into_bytes();
+
+    assert_eq!(cpio_reader::iter_files(&newc).count(), 1);
+
+    let entries = cpio_reader::iter_files_mut(&mut newc)
+        .map(|entry| (entry.name().to_owned(), entry.file().len()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(entries, [("ab".to_owned(), 0)]);
+}
@@ -0,0 +1,234 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{Endianness, Entry, EntryBuilder, Format, Mode, WriteError},
+    proptest::prelude::*,
+};
+
+const FORMATS: [Format; 5] = [
+    Format::OldBinary(Endianness::Little),
+    Format::OldBinary(Endianness::Big),
+    Format::PortableAscii,
+    Format::NewAscii,
+    Format::NewCrc,
+];
+
+const FILE_TYPES: [Mode; 7] = [
+    Mode::REGULAR_FILE,
+    Mode::DIRECTORY,
+    Mode::SYMBOLIK_LINK,
+    Mode::CHARACTER_SPECIAL_DEVICE,
+    Mode::BLOCK_SPECIAL_DEVICE,
+    Mode::NAMED_PIPE_FIFO,
+    Mode::SOCKET,
+];
+
+/// The values of the fields of an entry, independent of the format.
+#[derive(Clone, Debug)]
+struct Fields {
+    name: String,
+    mode: Mode,
+    ino: u32,
+    uid: u32,
+    gid: u32,
+    nlink: u32,
+    mtime: u64,
+    dev: u32,
+    rdev: u32,
+    devmajor: u32,
+    devminor: u32,
+    rdevmajor: u32,
+    rdevminor: u32,
+    file: Vec<u8>,
+}
+impl Fields {
+    fn build(&self, format: Format) -> Entry<'_> {
+        let builder = EntryBuilder::new(&self.name, format)
+            .mode(self.mode)
+            .ino(self.ino)
+            .uid(self.uid)
+            .gid(self.gid)
+            .nlink(self.nlink)
+            .mtime(self.mtime)
+            .file(&self.file);
+
+        match format {
+            Format::OldBinary(_) | Format::PortableAscii => builder.dev(self.dev).rdev(self.rdev),
+            Format::NewAscii | Format::NewCrc => builder
+                .devmajor(self.devmajor)
+                .devminor(self.devminor)
+                .rdevmajor(self.rdevmajor)
+                .rdevminor(self.rdevminor),
+        }
+        .build()
+    }
+
+    /// Returns `true` if all fields can be represented in `format`.
+    fn fits(&self, format: Format) -> bool {
+        let namesize = self.name.len() as u64 + 1;
+        let filesize = self.file.len() as u64;
+
+        let (small, large): (&[u64], &[u64]) = (
+            &[
+                self.dev.into(),
+                self.ino.into(),
+                self.mode.bits().into(),
+                self.uid.into(),
+                self.gid.into(),
+                self.nlink.into(),
+                self.rdev.into(),
+                namesize,
+            ],
+            &[self.mtime, filesize],
+        );
+
+        let (small_max, large_max) = match format {
+            Format::OldBinary(_) => (u16::MAX.into(), u32::MAX.into()),
+            Format::PortableAscii => (0o777_777, 0o777_7777_7777),
+            Format::NewAscii | Format::NewCrc => (u32::MAX.into(), u32::MAX.into()),
+        };
+
+        small.iter().all(|value| *value <= small_max)
+            && large.iter().all(|value| *value <= large_max)
+    }
+}
+
+/// Generates a value which often fits in 16 bits, the smallest field of all formats.
+fn number() -> impl Strategy<Value = u32> {
+    prop_oneof![
+        0..=0xff_u32,
+        0..=0o777_777_u32,
+        0..=0xffff_u32,
+        any::<u32>()
+    ]
+}
+
+fn fields() -> impl Strategy<Value = Fields> {
+    let name = "[a-zA-Z0-9._-]{1,16}(/[a-zA-Z0-9._-]{1,16}){0,4}";
+    let mode = (0..FILE_TYPES.len(), 0..=0o7777_u32).prop_map(|(file_type, permissions)| {
+        FILE_TYPES[file_type] | Mode::from_bits(permissions).unwrap()
+    });
+    let mtime = prop_oneof![0..=u64::from(u32::MAX), any::<u64>()];
+    let file = prop::collection::vec(any::<u8>(), 0..300);
+
+    (
+        (name, mode, number(), number(), number(), number(), mtime),
+        (number(), number(), number(), number(), number(), number()),
+        file,
+    )
+        .prop_map(
+            |(
+                (name, mode, ino, uid, gid, nlink, mtime),
+                (dev, rdev, devmajor, devminor, rdevmajor, rdevminor),
+                file,
+            )| Fields {
+                name,
+                mode,
+                ino,
+                uid,
+                gid,
+                nlink,
+                mtime,
+                dev,
+                rdev,
+                devmajor,
+                devminor,
+                rdevmajor,
+                rdevminor,
+                file,
+            },
+        )
+}
+
+fn assert_same_fields(
+    entry: &Entry<'_>,
+    fields: &Fields,
+    format: Format,
+) -> Result<(), TestCaseError> {
+    prop_assert_eq!(entry.format(), format);
+    prop_assert_eq!(entry.name(), &fields.name);
+    prop_assert_eq!(entry.mode(), fields.mode);
+    prop_assert_eq!(entry.ino(), fields.ino);
+    prop_assert_eq!(entry.uid(), fields.uid);
+    prop_assert_eq!(entry.gid(), fields.gid);
+    prop_assert_eq!(entry.nlink(), fields.nlink);
+    prop_assert_eq!(entry.mtime(), fields.mtime);
+    prop_assert_eq!(entry.file(), &fields.file[..]);
+
+    match format {
+        Format::OldBinary(_) | Format::PortableAscii => {
+            prop_assert_eq!(entry.dev(), Some(fields.dev));
+            prop_assert_eq!(entry.rdev(), Some(fields.rdev));
+        }
+        Format::NewAscii | Format::NewCrc => {
+            prop_assert_eq!(entry.devmajor(), Some(fields.devmajor));
+            prop_assert_eq!(entry.devminor(), Some(fields.devminor));
+            prop_assert_eq!(entry.rdevmajor(), Some(fields.rdevmajor));
+            prop_assert_eq!(entry.rdevminor(), Some(fields.rdevminor));
+        }
+    }
+
+    if format == Format::NewCrc {
+        prop_assert_eq!(entry.check(), Some(entry.computed_checksum()));
+    } else {
+        prop_assert_eq!(entry.check(), None);
+    }
+
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn written_entries_are_read_back(entries in prop::collection::vec(fields(), 0..8)) {
+        for format in FORMATS {
+            let mut cpio = Vec::new();
+
+            let mut written = Vec::new();
+
+            for fields in &entries {
+                let result = fields.build(format).write_to(&mut cpio);
+
+                if fields.fits(format) {
+                    prop_assert_eq!(result, Ok(()));
+
+                    written.push(fields);
+                } else {
+                    prop_assert!(matches!(result, Err(WriteError::Unrepresentable(_))));
+                }
+            }
+
+            cpio_reader::write_trailer(format, &mut cpio).unwrap();
+
+            let read = cpio_reader::iter_files(&cpio).collect::<Vec<_>>();
+
+            prop_assert_eq!(read.len(), written.len(), "{:?}", format);
+
+            for (entry, fields) in read.iter().zip(written) {
+                assert_same_fields(entry, fields, format)?;
+            }
+        }
+    }
+
+    #[test]
+    fn entries_are_written_back_byte_identically(entries in prop::collection::vec(fields(), 0..8)) {
+        for format in FORMATS {
+            let mut cpio = Vec::new();
+
+            for fields in entries.iter().filter(|fields| fields.fits(format)) {
+                fields.build(format).write_to(&mut cpio).unwrap();
+            }
+
+            cpio_reader::write_trailer(format, &mut cpio).unwrap();
+
+            let mut rewritten = Vec::new();
+
+            for entry in cpio_reader::iter_files(&cpio) {
+                entry.write_to(&mut rewritten).unwrap();
+            }
+
+            cpio_reader::write_trailer(format, &mut rewritten).unwrap();
+
+            prop_assert_eq!(&rewritten, &cpio, "{:?}", format);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{Entry, Mode, RpmError, RpmPayload},
+    std::fs,
+};
+
+#[test]
+fn read_compressed_payloads() {
+    let newc = fs::read("tests/newc.cpio").unwrap();
+
+    for (rpm, compressor) in [("tests/gzip.rpm", "gzip"), ("tests/xz.rpm", "xz")] {
+        let payload = RpmPayload::new(&fs::read(rpm).unwrap()).unwrap();
+
+        assert_eq!(payload.compressor(), compressor);
+        assert!(!payload.is_stripped());
+        assert_eq!(payload.payload(), newc);
+        assert!(payload.iter_files().eq(cpio_reader::iter_files(&newc)));
+    }
+}
+
+#[test]
+fn read_stripped_payload() {
+    let payload = RpmPayload::new(&fs::read("tests/stripped.rpm").unwrap()).unwrap();
+
+    assert!(payload.is_stripped());
+
+    let entries = payload.iter_files().collect::<Vec<_>>();
+
+    let names = entries.iter().map(Entry::name).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "./usr/a",
+            "./usr/b",
+            "./usr/bin/link",
+            "./usr/bin/tool",
+            "./usr"
+        ]
+    );
+
+    assert_eq!(entries[0].file(), b"");
+    assert_eq!(entries[0].nlink(), 2);
+    assert_eq!(entries[1].file(), b"shared");
+    assert_eq!(entries[1].ino(), entries[0].ino());
+
+    assert!(entries[2].mode().contains(Mode::SYMBOLIK_LINK));
+    assert_eq!(entries[2].file(), b"tool");
+
+    assert_eq!(entries[3].file(), b"hello\n");
+    assert_eq!(entries[3].mode(), Mode::from_bits(0o100_755).unwrap());
+    assert_eq!(entries[3].mtime(), 1_700_000_002);
+
+    assert!(entries[4].mode().contains(Mode::DIRECTORY));
+    assert_eq!(entries[4].file(), b"");
+}
+
+#[test]
+fn reject_non_rpm_files() {
+    let newc = fs::read("tests/newc.cpio").unwrap();
+
+    assert!(matches!(RpmPayload::new(&newc), Err(RpmError::InvalidLead)));
+
+    let mut rpm = fs::read("tests/gzip.rpm").unwrap();
+    rpm.truncate(100);
+
+    assert!(matches!(
+        RpmPayload::new(&rpm),
+        Err(RpmError::InvalidHeader)
+    ));
+}
@@ -0,0 +1,88 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{EntryBuilder, Format, LimitError, Mode},
+    serde_json::json,
+};
+
+#[test]
+fn entries_are_serialized() {
+    let entry = EntryBuilder::new("bin/sh", Format::NewAscii)
+        .mode(Mode::SYMBOLIK_LINK | Mode::from_bits(0o777).unwrap())
+        .uid(1000)
+        .mtime(1_629_722_096)
+        .rdevmajor(8)
+        .rdevminor(2)
+        .file(b"dash")
+        .build();
+
+    let expected = json!({
+        "name": "bin/sh",
+        "format": "NewAscii",
+        "mode": { "bits": 0o120_777, "symbolic": "lrwxrwxrwx" },
+        "ino": 0,
+        "uid": 1000,
+        "gid": 0,
+        "nlink": 1,
+        "mtime": 1_629_722_096,
+        "device": { "major": 0, "minor": 0 },
+        "rdevice": { "major": 8, "minor": 2 },
+        "size": 4,
+        "check": null,
+        "symlink_target": "dash",
+    });
+
+    assert_eq!(serde_json::to_value(entry).unwrap(), expected);
+
+    let mut expected = expected;
+    expected["content"] = json!("ZGFzaA==");
+
+    assert_eq!(
+        serde_json::to_value(entry.with_content()).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn contents_are_encoded_in_base64() {
+    let cases: [(&[u8], &str); 5] = [
+        (b"", ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"\xfb\xff\xbf\x00", "+/+/AA=="),
+    ];
+
+    for (file, encoded) in cases {
+        let entry = EntryBuilder::new("file", Format::NewAscii)
+            .file(file)
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(entry.with_content()).unwrap()["content"],
+            encoded
+        );
+    }
+}
+
+#[test]
+fn modes_formats_and_errors_are_serialized() {
+    let mode = Mode::DIRECTORY | Mode::STICKY | Mode::from_bits(0o777).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(mode).unwrap(),
+        json!({ "bits": 0o41_777, "symbolic": "drwxrwxrwt" })
+    );
+    assert_eq!(
+        serde_json::to_value(Mode::REGULAR_FILE | Mode::SUID | Mode::SGID).unwrap()["symbolic"],
+        "---S--S---"
+    );
+    assert_eq!(
+        serde_json::to_value(Format::OldBinary(cpio_reader::Endianness::Little)).unwrap(),
+        json!({ "OldBinary": "Little" })
+    );
+    assert_eq!(
+        serde_json::to_value(LimitError::TooManyEntries { offset: 512 }).unwrap(),
+        json!({ "TooManyEntries": { "offset": 512 } })
+    );
+}
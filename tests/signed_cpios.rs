@@ -0,0 +1,70 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{AppendedSignature, SignatureError, VerifiedArchive},
+    ed25519_dalek::{Signer, SigningKey},
+    std::{convert::TryFrom, fs},
+};
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[7; 32])
+}
+
+#[test]
+fn ed25519_signatures_are_verified() {
+    let cpio = fs::read("tests/newc.cpio").unwrap();
+    let key = signing_key();
+    let signature = key.sign(&cpio).to_bytes();
+    let public_key = key.verifying_key().to_bytes();
+
+    let verified = VerifiedArchive::verify_ed25519(&cpio, &signature, &public_key).unwrap();
+
+    assert_eq!(verified.as_bytes(), cpio);
+    assert!(verified.iter_files().eq(cpio_reader::iter_files(&cpio)));
+
+    let mut tampered = cpio.clone();
+    tampered[200] ^= 1;
+
+    assert_eq!(
+        VerifiedArchive::verify_ed25519(&tampered, &signature, &public_key),
+        Err(SignatureError::Mismatch)
+    );
+    assert_eq!(
+        VerifiedArchive::verify_ed25519(&cpio, &signature[1..], &public_key),
+        Err(SignatureError::MalformedSignature)
+    );
+    assert_eq!(
+        VerifiedArchive::verify_ed25519(&cpio, &signature, &public_key[1..]),
+        Err(SignatureError::MalformedKey)
+    );
+}
+
+#[test]
+fn appended_signatures_are_split() {
+    let cpio = fs::read("tests/newc.cpio").unwrap();
+    let signature = b"PKCS#7 blob";
+
+    let mut signed = cpio.clone();
+    signed.extend_from_slice(signature);
+    signed.extend_from_slice(&[0, 0, AppendedSignature::PKCS7, 0, 0, 0, 0, 0]);
+    signed.extend_from_slice(&u32::try_from(signature.len()).unwrap().to_be_bytes());
+    signed.extend_from_slice(b"~Module signature appended~\n");
+
+    let (payload, appended) = AppendedSignature::split(&signed).unwrap();
+
+    assert_eq!(payload, cpio);
+    assert_eq!(appended.signature(), signature);
+    assert_eq!(appended.id_type(), AppendedSignature::PKCS7);
+
+    let verified =
+        VerifiedArchive::verify_with(payload, |payload| payload == cpio.as_slice()).unwrap();
+
+    assert_eq!(verified.as_bytes(), cpio);
+    assert_eq!(
+        VerifiedArchive::verify_with(payload, |_| false),
+        Err(SignatureError::Mismatch)
+    );
+
+    assert_eq!(AppendedSignature::split(&cpio), None);
+    assert_eq!(AppendedSignature::split(&signed[signed.len() - 30..]), None);
+}
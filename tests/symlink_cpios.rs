@@ -0,0 +1,79 @@
+#![allow(missing_docs)]
+
+use cpio_reader::{EntryBuilder, Format, Mode, ResolveError, SymlinkResolver};
+
+/// Returns a cpio file with the given entries, which are symbolic links if the targets are given
+/// and regular files otherwise.
+fn archive(entries: &[(&str, Option<&[u8]>)]) -> Vec<u8> {
+    let mut cpio = Vec::new();
+
+    for (name, target) in entries {
+        let builder = EntryBuilder::new(name, Format::NewAscii);
+
+        let builder = match target {
+            Some(target) => builder.mode(Mode::SYMBOLIK_LINK).file(target),
+            None => builder.mode(Mode::REGULAR_FILE).file(name.as_bytes()),
+        };
+
+        builder.build().write_to(&mut cpio).unwrap();
+    }
+
+    cpio_reader::write_trailer(Format::NewAscii, &mut cpio).unwrap();
+
+    cpio
+}
+
+#[test]
+fn symlink_target() {
+    let cpio = archive(&[("link", Some(b"target")), ("file", None)]);
+    let entries = cpio_reader::iter_files(&cpio).collect::<Vec<_>>();
+
+    assert_eq!(entries[0].symlink_target(), Some("target"));
+    assert_eq!(entries[1].symlink_target(), None);
+}
+
+#[test]
+fn absolute_and_relative_symlinks_are_followed() {
+    let cpio = archive(&[
+        ("./sbin", Some(b"usr/sbin")),
+        ("./lib", Some(b"usr/lib")),
+        ("./usr/sbin/init", Some(b"../../lib/systemd/systemd")),
+        ("./usr/lib/systemd/systemd", None),
+        ("./bin/sh", Some(b"/usr/bin/../bin/dash")),
+        ("./usr/bin/dash", None),
+    ]);
+    let resolver = SymlinkResolver::new(&cpio);
+
+    let init = resolver.resolve("/sbin/init").unwrap();
+
+    assert_eq!(init.name(), "./usr/lib/systemd/systemd");
+    assert_eq!(resolver.resolve("sbin/../lib/systemd/systemd"), Ok(init));
+    assert_eq!(resolver.resolve("bin/sh").unwrap().name(), "./usr/bin/dash");
+    assert_eq!(
+        resolver.get("/sbin").unwrap().symlink_target(),
+        Some("usr/sbin")
+    );
+    assert_eq!(resolver.get("/sbin/init"), None);
+}
+
+#[test]
+fn resolution_errors() {
+    let cpio = archive(&[
+        ("a", Some(b"b")),
+        ("b", Some(b"./a")),
+        ("dangling", Some(b"nowhere")),
+        ("invalid", Some(b"\xff")),
+    ]);
+    let resolver = SymlinkResolver::new(&cpio);
+
+    assert!(matches!(
+        resolver.resolve("a"),
+        Err(ResolveError::TooManySymlinks { .. })
+    ));
+    assert_eq!(resolver.resolve("dangling"), Err(ResolveError::NotFound));
+    assert_eq!(resolver.resolve("missing"), Err(ResolveError::NotFound));
+    assert!(matches!(
+        resolver.resolve("invalid"),
+        Err(ResolveError::InvalidTarget { .. })
+    ));
+}
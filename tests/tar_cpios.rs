@@ -0,0 +1,147 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{EntryBuilder, Format, Mode},
+    std::{fs, io::Read},
+};
+
+const CPIOS: [&str; 4] = [
+    "tests/bin.cpio",
+    "tests/odc.cpio",
+    "tests/newc.cpio",
+    "tests/crc.cpio",
+];
+
+#[test]
+fn round_trip_through_tar() {
+    for cpio in CPIOS {
+        let original = fs::read(cpio).unwrap();
+        let format = cpio_reader::iter_files(&original).next().unwrap().format();
+
+        let tar = cpio_reader::cpio_to_tar(&original, Vec::new()).unwrap();
+        let converted = cpio_reader::tar_to_cpio(&tar[..], format, Vec::new()).unwrap();
+
+        assert_eq!(converted.len() % 512, 0);
+
+        let originals = cpio_reader::iter_files(&original).collect::<Vec<_>>();
+        let converteds = cpio_reader::iter_files(&converted).collect::<Vec<_>>();
+
+        assert_eq!(originals.len(), converteds.len(), "{cpio}");
+
+        for (original, converted) in originals.iter().zip(&converteds) {
+            assert_eq!(converted.name(), original.name());
+            assert_eq!(converted.file(), original.file());
+            assert_eq!(converted.mode(), original.mode());
+            assert_eq!(converted.uid(), original.uid());
+            assert_eq!(converted.gid(), original.gid());
+            assert_eq!(converted.nlink(), original.nlink());
+            assert_eq!(converted.mtime(), original.mtime());
+        }
+    }
+}
+
+#[test]
+fn long_names_and_large_values_use_pax_headers() {
+    let name = "long/".repeat(40) + "name";
+    let target = "target/".repeat(30);
+
+    let mut cpio = Vec::new();
+
+    EntryBuilder::new(&name, Format::NewAscii)
+        .mode(Mode::REGULAR_FILE | Mode::USER_READABLE)
+        .uid(0x1234_5678)
+        .gid(0x8765_4321)
+        .file(b"content")
+        .build()
+        .write_to(&mut cpio)
+        .unwrap();
+    EntryBuilder::new("link", Format::NewAscii)
+        .mode(Mode::SYMBOLIK_LINK)
+        .file(target.as_bytes())
+        .build()
+        .write_to(&mut cpio)
+        .unwrap();
+    cpio_reader::write_trailer(Format::NewAscii, &mut cpio).unwrap();
+
+    let tar = cpio_reader::cpio_to_tar(&cpio, Vec::new()).unwrap();
+
+    let mut archive = tar::Archive::new(&tar[..]);
+    let mut entries = archive.entries().unwrap().map(Result::unwrap);
+
+    let mut file = entries.next().unwrap();
+    assert_eq!(&*file.path_bytes(), name.as_bytes());
+
+    let mut content = Vec::new();
+    file.read_to_end(&mut content).unwrap();
+    assert_eq!(content, b"content");
+
+    let link = entries.next().unwrap();
+    assert_eq!(&*link.link_name_bytes().unwrap(), target.as_bytes());
+
+    let converted = cpio_reader::tar_to_cpio(&tar[..], Format::NewCrc, Vec::new()).unwrap();
+    let entries = cpio_reader::iter_files(&converted).collect::<Vec<_>>();
+
+    assert_eq!(entries[0].name(), name);
+    assert_eq!(entries[0].uid(), 0x1234_5678);
+    assert_eq!(entries[0].gid(), 0x8765_4321);
+    assert_eq!(entries[0].file(), b"content");
+    assert_eq!(entries[1].mode(), Mode::SYMBOLIK_LINK);
+    assert_eq!(entries[1].file(), target.as_bytes());
+}
+
+#[test]
+fn hard_links_and_devices_are_preserved() {
+    let mut cpio = Vec::new();
+
+    for (name, file) in [("a", &b""[..]), ("b", &b"shared"[..])] {
+        EntryBuilder::new(name, Format::NewAscii)
+            .mode(Mode::REGULAR_FILE | Mode::USER_READABLE)
+            .ino(42)
+            .nlink(2)
+            .file(file)
+            .build()
+            .write_to(&mut cpio)
+            .unwrap();
+    }
+    EntryBuilder::new("tty", Format::NewAscii)
+        .mode(Mode::CHARACTER_SPECIAL_DEVICE | Mode::USER_READABLE)
+        .rdevmajor(4)
+        .rdevminor(1)
+        .build()
+        .write_to(&mut cpio)
+        .unwrap();
+    cpio_reader::write_trailer(Format::NewAscii, &mut cpio).unwrap();
+
+    let tar = cpio_reader::cpio_to_tar(&cpio, Vec::new()).unwrap();
+
+    let mut archive = tar::Archive::new(&tar[..]);
+    let headers = archive
+        .entries()
+        .unwrap()
+        .map(|entry| entry.unwrap().header().clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(headers[0].entry_type(), tar::EntryType::Regular);
+    assert_eq!(headers[0].size().unwrap(), 6);
+    assert_eq!(headers[1].entry_type(), tar::EntryType::Link);
+    assert_eq!(&*headers[1].link_name_bytes().unwrap(), b"a");
+    assert_eq!(headers[2].device_major().unwrap(), Some(4));
+    assert_eq!(headers[2].device_minor().unwrap(), Some(1));
+
+    let newc = cpio_reader::tar_to_cpio(&tar[..], Format::NewAscii, Vec::new()).unwrap();
+    let entries = cpio_reader::iter_files(&newc).collect::<Vec<_>>();
+
+    assert_eq!(entries[0].ino(), entries[1].ino());
+    assert_eq!(entries[0].nlink(), 2);
+    assert_eq!(entries[0].file(), b"");
+    assert_eq!(entries[1].file(), b"shared");
+    assert_eq!(entries[2].rdevmajor(), Some(4));
+    assert_eq!(entries[2].rdevminor(), Some(1));
+
+    let odc = cpio_reader::tar_to_cpio(&tar[..], Format::PortableAscii, Vec::new()).unwrap();
+    let entries = cpio_reader::iter_files(&odc).collect::<Vec<_>>();
+
+    assert_eq!(entries[0].file(), b"shared");
+    assert_eq!(entries[1].file(), b"shared");
+    assert_eq!(entries[2].rdev(), Some(0x401));
+}
@@ -0,0 +1,103 @@
+#![allow(missing_docs)]
+
+use {
+    cpio_reader::{EntryBuilder, Format, Timestamp},
+    std::time::{Duration, UNIX_EPOCH},
+};
+
+/// 2021-08-23T12:34:56Z.
+const RELEASE: u64 = 1_629_722_096;
+
+#[test]
+fn display() {
+    assert_eq!(Timestamp::UNIX_EPOCH.to_string(), "1970-01-01T00:00:00Z");
+    assert_eq!(
+        Timestamp::from_secs(RELEASE).to_string(),
+        "2021-08-23T12:34:56Z"
+    );
+    assert_eq!(
+        Timestamp::from_secs(u32::MAX.into()).to_string(),
+        "2106-02-07T06:28:15Z"
+    );
+    assert_eq!(
+        Timestamp::from_secs(951_782_400).to_string(),
+        "2000-02-29T00:00:00Z"
+    );
+}
+
+#[test]
+fn display_ls() {
+    let timestamp = Timestamp::from_secs(RELEASE);
+
+    assert_eq!(
+        timestamp
+            .display_ls(Timestamp::from_secs(RELEASE + 86400))
+            .to_string(),
+        "Aug 23 12:34"
+    );
+    assert_eq!(
+        timestamp
+            .display_ls(Timestamp::from_secs(RELEASE + 365 * 86400))
+            .to_string(),
+        "Aug 23  2021"
+    );
+    assert_eq!(
+        timestamp
+            .display_ls(Timestamp::from_secs(RELEASE - 1))
+            .to_string(),
+        "Aug 23  2021"
+    );
+}
+
+#[test]
+fn conversions() {
+    let timestamp = Timestamp::from_secs(RELEASE);
+
+    assert_eq!(
+        timestamp.to_system_time(),
+        Some(UNIX_EPOCH + Duration::from_secs(RELEASE))
+    );
+    assert_eq!(timestamp.to_chrono().unwrap().timestamp(), 1_629_722_096);
+    assert_eq!(
+        timestamp.to_offset_date_time().unwrap().unix_timestamp(),
+        1_629_722_096
+    );
+
+    assert_eq!(Timestamp::from_secs(u64::MAX).to_chrono(), None);
+    assert_eq!(Timestamp::from_secs(u64::MAX).to_offset_date_time(), None);
+}
+
+#[test]
+fn times_after_2106_are_recovered() {
+    // 2110-01-01T00:00:00Z, which does not fit in 32 bits.
+    let after_2106 = 4_418_064_000_u64;
+    let wrapped = after_2106 & u64::from(u32::MAX);
+
+    let mut cpio = Vec::new();
+    EntryBuilder::new("file", Format::NewAscii)
+        .mtime(wrapped)
+        .build()
+        .write_to(&mut cpio)
+        .unwrap();
+
+    let entry = cpio_reader::iter_files(&cpio).next().unwrap();
+
+    assert_eq!(entry.modified(), Timestamp::from_secs(wrapped));
+    assert_eq!(
+        entry.modified_near(Timestamp::from_secs(after_2106 - 86400)),
+        Timestamp::from_secs(after_2106)
+    );
+    assert_eq!(
+        entry.modified_near(Timestamp::from_secs(RELEASE)),
+        Timestamp::from_secs(wrapped)
+    );
+
+    assert_eq!(
+        Timestamp::from_secs(u64::MAX).nearest_wrapped(32, Timestamp::UNIX_EPOCH),
+        Timestamp::from_secs(u64::MAX)
+    );
+    assert_eq!(
+        Timestamp::from_secs(5).nearest_wrapped(64, Timestamp::from_secs(RELEASE)),
+        Timestamp::from_secs(5)
+    );
+}
@@ -0,0 +1,33 @@
+#![allow(missing_docs)]
+
+use std::fs;
+
+#[test]
+fn round_trip_is_byte_exact() {
+    for cpio in [
+        "tests/bin.cpio",
+        "tests/odc.cpio",
+        "tests/newc.cpio",
+        "tests/crc.cpio",
+    ] {
+        let original = fs::read(cpio).unwrap();
+
+        let mut written = Vec::new();
+        let mut format = None;
+
+        for entry in cpio_reader::iter_files(&original) {
+            entry.write_to(&mut written).unwrap();
+
+            assert_eq!(written.len(), entry.header_offset() + entry.entry_len());
+
+            format = Some(entry.format());
+        }
+
+        cpio_reader::write_trailer(format.unwrap(), &mut written).unwrap();
+
+        // GNU cpio pads the file to a multiple of 512 bytes.
+        written.resize(written.len().div_ceil(512) * 512, 0);
+
+        assert_eq!(written, original, "{cpio}");
+    }
+}